@@ -0,0 +1,57 @@
+// Word parallel `BitGrid::step` against a cell at a time with `get_surround`.  Run with
+// `cargo bench --bench life`.
+use helper::{BitGrid, LifeRule};
+use std::time::Instant;
+
+const SIZE: isize = 512;
+const GENERATIONS: usize = 20;
+
+// R-pentominoes close enough together to interact straight away
+fn soup() -> BitGrid {
+    let mut grid = BitGrid::new();
+    for y in (0..SIZE).step_by(8) {
+        for x in (0..SIZE).step_by(8) {
+            for (dx, dy) in [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)] {
+                grid.set_bit(x + dx, y + dy);
+            }
+        }
+    }
+    grid
+}
+
+fn step_cells(grid: &mut BitGrid, rule: &LifeRule) -> BitGrid {
+    let mut next = BitGrid::new();
+    let Some((min, max)) = grid.set_bounds() else {
+        return next;
+    };
+    for y in min.1 - 1..=max.1 + 1 {
+        for x in min.0 - 1..=max.0 + 1 {
+            if rule.next(grid.get_surround(x, y)) {
+                next.set_bit(x, y);
+            }
+        }
+    }
+    next
+}
+
+fn main() {
+    let conway = LifeRule::conway();
+    let table = LifeRule::from_table(|s| conway.next(s));
+
+    let start = Instant::now();
+    let mut cells = soup();
+    for _ in 0..GENERATIONS {
+        cells = step_cells(&mut cells, &conway);
+    }
+    println!("{:13} {:?}", "get_surround", start.elapsed());
+
+    for (name, rule) in [("counts", &conway), ("table", &table)] {
+        let start = Instant::now();
+        let mut words = soup();
+        for _ in 0..GENERATIONS {
+            words = words.step(rule);
+        }
+        println!("{:13} {:?}", format!("step {name}"), start.elapsed());
+        assert_eq!(words.count_set(), cells.count_set());
+    }
+}
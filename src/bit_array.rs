@@ -1,15 +1,23 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound, Not, RangeBounds,
+};
+
+const BITS: usize = usize::BITS as usize;
+
+// Bits past `len` are always clear, so whole words can be compared, counted and combined
 #[derive(Clone)]
 pub struct BitArray {
     array: Vec<usize>,
-    cap: usize,
+    len: usize,
 }
 
 impl BitArray {
     pub fn new(len: usize) -> Self {
-        let cap = ((len + usize::BITS as usize - 1) / usize::BITS as usize) * usize::BITS as usize;
         Self {
-            array: vec![0; cap / usize::BITS as usize],
-            cap,
+            array: vec![0; len.div_ceil(BITS)],
+            len,
         }
     }
 
@@ -21,17 +29,61 @@ impl BitArray {
                 .for_each(|(s, o)| *s = *o);
         } else {
             self.array = other.array.clone();
-            self.cap = other.cap;
         }
+        self.len = other.len;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Bits that fit without allocating
     pub fn capacity(&self) -> usize {
-        self.cap
+        self.array.len() * BITS
+    }
+
+    // Growing adds clear bits, shrinking keeps the capacity
+    pub fn resize(&mut self, len: usize) {
+        let words = len.div_ceil(BITS);
+        if words > self.array.len() {
+            self.array.resize(words, 0);
+        }
+        if len < self.len {
+            if !len.is_multiple_of(BITS) {
+                self.array[words - 1] &= (1 << (len % BITS)) - 1;
+            }
+            self.array[words..].iter_mut().for_each(|v| *v = 0);
+        }
+        self.len = len;
+    }
+
+    pub fn push(&mut self, set: bool) {
+        let bit_idx = self.len;
+        self.resize(self.len + 1);
+        if set {
+            self.set(bit_idx, true);
+        }
+    }
+
+    // Empties the array, keeping the capacity
+    pub fn clear(&mut self) {
+        self.resize(0);
     }
 
+    // Setting a bit past the end grows the array to hold it
     pub fn set(&mut self, bit_idx: usize, set: bool) -> bool {
-        let idx = bit_idx / usize::BITS as usize;
-        let bit = bit_idx % usize::BITS as usize;
+        if bit_idx >= self.len {
+            if !set {
+                return false;
+            }
+            self.resize(bit_idx + 1);
+        }
+        let idx = bit_idx / BITS;
+        let bit = bit_idx % BITS;
 
         let entry = &mut self.array[idx];
         let prev = *entry & (1 << bit) != 0;
@@ -44,16 +96,343 @@ impl BitArray {
         prev
     }
 
+    // Bits past the end are clear
     pub fn get(&self, bit_idx: usize) -> bool {
-        let idx = bit_idx / usize::BITS as usize;
-        let bit = bit_idx % usize::BITS as usize;
+        if bit_idx >= self.len {
+            return false;
+        }
+        let idx = bit_idx / BITS;
+        let bit = bit_idx % BITS;
 
         self.array[idx] & (1 << bit) != 0
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|bit_idx| self.get(bit_idx))
+    }
+
+    pub fn iter_set(&self) -> SetBits<'_> {
+        SetBits::new(&self.array)
+    }
+
     pub fn popcount(&self) -> usize {
         self.array
             .iter()
             .fold(0, |popcount, v| popcount + v.count_ones() as usize)
     }
+
+    // Set bits within `range`, which is clamped to the array
+    pub fn count_ones(&self, range: impl RangeBounds<usize>) -> usize {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        }
+        .min(self.len);
+        if start >= end {
+            return 0;
+        }
+
+        let (first, last) = (start / BITS, (end - 1) / BITS);
+        (first..=last)
+            .map(|idx| {
+                let mut v = self.array[idx];
+                if idx == first {
+                    v &= usize::MAX << (start % BITS);
+                }
+                if idx == last {
+                    v &= usize::MAX >> (BITS - 1 - (end - 1) % BITS);
+                }
+                v.count_ones() as usize
+            })
+            .sum()
+    }
+
+    // Set bits before `bit_idx`
+    pub fn rank(&self, bit_idx: usize) -> usize {
+        self.count_ones(..bit_idx)
+    }
+
+    // Index of the set bit with rank `n`, the first set bit being rank 0
+    pub fn select(&self, mut n: usize) -> Option<usize> {
+        for (idx, &v) in self.array.iter().enumerate() {
+            let ones = v.count_ones() as usize;
+            if n < ones {
+                let mut v = v;
+                for _ in 0..n {
+                    v &= v - 1;
+                }
+                return Some(idx * BITS + v.trailing_zeros() as usize);
+            }
+            n -= ones;
+        }
+        None
+    }
+
+    pub fn first_set(&self) -> Option<usize> {
+        self.next_set_from(0)
+    }
+
+    pub fn next_set_after(&self, bit_idx: usize) -> Option<usize> {
+        self.next_set_from(bit_idx + 1)
+    }
+
+    fn next_set_from(&self, bit_idx: usize) -> Option<usize> {
+        if bit_idx >= self.len {
+            return None;
+        }
+        let mut idx = bit_idx / BITS;
+        let mut v = self.array[idx] & (usize::MAX << (bit_idx % BITS));
+        while v == 0 {
+            idx += 1;
+            v = *self.array.get(idx)?;
+        }
+        Some(idx * BITS + v.trailing_zeros() as usize)
+    }
+
+    // Words holding bits before `len`, the rest are clear
+    fn words(&self) -> &[usize] {
+        &self.array[..self.len.div_ceil(BITS)]
+    }
+}
+
+impl PartialEq for BitArray {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.words() == other.words()
+    }
+}
+
+impl Eq for BitArray {}
+
+impl Hash for BitArray {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.words().hash(state);
+    }
+}
+
+impl fmt::Debug for BitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BitArray")
+            .field("len", &self.len)
+            .field("set", &self.iter_set().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+// Arrays of different lengths combine as though the shorter one were padded with clear bits, so
+// the result has the longer length
+macro_rules! impl_op {
+    ($op_trait:ident, $op:ident, $assign_trait:ident, $assign:ident) => {
+        impl $op_trait<&BitArray> for &BitArray {
+            type Output = BitArray;
+
+            fn $op(self, rhs: &BitArray) -> Self::Output {
+                let mut result = self.clone();
+                result.$assign(rhs);
+                result
+            }
+        }
+
+        impl $assign_trait<&BitArray> for BitArray {
+            fn $assign(&mut self, rhs: &BitArray) {
+                if self.len < rhs.len {
+                    self.resize(rhs.len);
+                }
+                self.array
+                    .iter_mut()
+                    .zip(rhs.array.iter().chain(std::iter::repeat(&0)))
+                    .for_each(|(s, o)| s.$assign(o));
+            }
+        }
+    };
+}
+
+impl_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+// Flips the bits before `len`
+impl Not for &BitArray {
+    type Output = BitArray;
+
+    fn not(self) -> Self::Output {
+        let mut inverted = BitArray {
+            array: self.array.iter().map(|v| !v).collect(),
+            len: self.array.len() * BITS,
+        };
+        inverted.resize(self.len);
+        inverted
+    }
+}
+
+// Indices of the set bits in `words`, scanning a word at a time
+#[derive(Clone)]
+pub struct SetBits<'a> {
+    words: &'a [usize],
+    index: usize,
+    word: usize,
+}
+
+impl<'a> SetBits<'a> {
+    pub(crate) fn new(words: &'a [usize]) -> Self {
+        Self {
+            words,
+            index: 0,
+            word: words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Iterator for SetBits<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.word == 0 {
+            self.index += 1;
+            self.word = *self.words.get(self.index)?;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.index * BITS + bit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iter_set() {
+        let mut array = BitArray::new(300);
+        assert_eq!(array.iter_set().count(), 0);
+        for i in [0, 3, 63, 64, 200, 299] {
+            array.set(i, true);
+        }
+        assert_eq!(
+            array.iter_set().collect::<Vec<_>>(),
+            [0, 3, 63, 64, 200, 299]
+        );
+        assert_eq!(array.iter_set().count(), array.popcount());
+    }
+
+    #[test]
+    fn ops() {
+        let mut a = BitArray::new(100);
+        let mut b = BitArray::new(200);
+        for i in [1, 64, 99] {
+            a.set(i, true);
+        }
+        for i in [64, 150] {
+            b.set(i, true);
+        }
+
+        assert_eq!((&a & &b).iter_set().collect::<Vec<_>>(), [64]);
+        assert_eq!((&a | &b).iter_set().collect::<Vec<_>>(), [1, 64, 99, 150]);
+        assert_eq!((&a ^ &b).iter_set().collect::<Vec<_>>(), [1, 99, 150]);
+        assert_eq!((&b & &a).capacity(), 256);
+        assert_eq!((!&a).popcount(), 100 - 3);
+        assert_eq!((!&a).len(), 100);
+
+        a ^= &b;
+        assert_eq!((a.len(), a.capacity()), (200, 256));
+        a &= &b;
+        assert_eq!(a.iter_set().collect::<Vec<_>>(), [150]);
+        a |= &b;
+        assert_eq!(a.popcount(), 2);
+    }
+
+    #[test]
+    fn growth() {
+        let mut array = BitArray::new(0);
+        assert!(array.is_empty());
+        assert!(!array.get(10));
+        assert!(!array.set(10, false));
+        assert_eq!(array.len(), 0);
+
+        for i in 0..70 {
+            array.push(i % 3 == 0);
+        }
+        assert_eq!(array.len(), 70);
+        assert_eq!(array.popcount(), 24);
+        assert_eq!(
+            array.iter().take(4).collect::<Vec<_>>(),
+            [true, false, false, true]
+        );
+        assert_eq!(array.iter().count(), 70);
+
+        // Setting past the end grows
+        assert!(!array.set(200, true));
+        assert_eq!(array.len(), 201);
+        assert!(array.capacity() >= 201);
+
+        // Shrinking clears what's cut off, so growing again doesn't bring it back
+        array.resize(66);
+        assert_eq!(array.popcount(), 22);
+        array.resize(300);
+        assert!(!array.get(66) && !array.get(200));
+        assert_eq!(array.popcount(), 22);
+
+        let capacity = array.capacity();
+        array.clear();
+        assert_eq!(
+            (array.len(), array.capacity(), array.popcount()),
+            (0, capacity, 0)
+        );
+    }
+
+    #[test]
+    fn rank_select() {
+        let mut array = BitArray::new(300);
+        let set = [0, 3, 63, 64, 200, 299];
+        for i in set {
+            array.set(i, true);
+        }
+        assert_eq!(array.count_ones(..), 6);
+        assert_eq!(array.count_ones(3..64), 2);
+        assert_eq!(array.count_ones(3..=64), 3);
+        assert_eq!(array.count_ones(64..64), 0);
+        assert_eq!(array.count_ones(250..1000), 1);
+        for (n, &i) in set.iter().enumerate() {
+            assert_eq!(array.rank(i), n);
+            assert_eq!(array.rank(i + 1), n + 1);
+            assert_eq!(array.select(n), Some(i));
+        }
+        assert_eq!(array.select(6), None);
+
+        assert_eq!(array.first_set(), Some(0));
+        let mut found = vec![];
+        let mut next = array.first_set();
+        while let Some(i) = next {
+            found.push(i);
+            next = array.next_set_after(i);
+        }
+        assert_eq!(found, set);
+        assert_eq!(BitArray::new(10).first_set(), None);
+    }
+
+    #[test]
+    fn eq_hash() {
+        use crate::HashSet;
+
+        let mut a = BitArray::new(10);
+        let mut b = BitArray::new(500);
+        b.resize(10);
+        assert_eq!(a, b);
+        a.set(3, true);
+        assert_ne!(a, b);
+        b.set(3, true);
+        assert_eq!(a, b);
+        b.push(false);
+        assert_ne!(a, b);
+
+        let visited: HashSet<BitArray> = [a.clone(), a.clone(), b].into_iter().collect();
+        assert_eq!(visited.len(), 2);
+        assert_eq!(format!("{a:?}"), "BitArray { len: 10, set: [3] }");
+    }
 }
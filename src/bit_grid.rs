@@ -1,3 +1,139 @@
+use crate::{Frame, Point2D, Render, SetBits};
+
+mod display;
+mod hash;
+mod life;
+mod nd;
+mod ops;
+mod region;
+mod sparse;
+mod transform;
+
+pub use display::BitGridDisplay;
+pub use life::LifeRule;
+pub use nd::{BitGrid3D, BitGrid4D, BitGridN};
+pub use sparse::SparseBitGrid;
+pub use transform::BitGridView;
+
+const BITS: usize = usize::BITS as usize;
+
+type Bounds = ((isize, isize), (isize, isize));
+
+// Bounds of every bit that has been set, which may include bits since cleared
+#[derive(Clone, Copy, Debug)]
+struct SetBounds {
+    min: (isize, isize),
+    max: (isize, isize),
+}
+
+impl Default for SetBounds {
+    fn default() -> Self {
+        Self {
+            min: (isize::MAX, isize::MAX),
+            max: (isize::MIN, isize::MIN),
+        }
+    }
+}
+
+impl SetBounds {
+    fn include(&mut self, x: isize, y: isize) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    fn get(&self) -> Option<Bounds> {
+        if self.min.0 > self.max.0 {
+            None
+        } else {
+            Some((self.min, self.max))
+        }
+    }
+}
+
+// Index of the first word of row `y`, for rows of `stride` words starting at `origin`
+fn row_start(grid: &[usize], origin: (isize, isize), stride: usize, y: isize) -> Option<usize> {
+    if stride == 0 || y < origin.1 {
+        return None;
+    }
+    let ny = (y - origin.1) as usize;
+    (ny < grid.len() / stride).then_some(ny * stride)
+}
+
+// `BITS` bits of row `y` starting at `x`, with bit 0 being `x`.  Anything outside of the grid is
+// clear.
+fn word_at(grid: &[usize], origin: (isize, isize), stride: usize, x: isize, y: isize) -> usize {
+    let Some(row) = row_start(grid, origin, stride, y) else {
+        return 0;
+    };
+    let nx = x - origin.0;
+    let index = nx.div_euclid(BITS as isize);
+    let shift = nx.rem_euclid(BITS as isize) as usize;
+    let word = |index: isize| {
+        if index >= 0 && (index as usize) < stride {
+            grid[row + index as usize]
+        } else {
+            0
+        }
+    };
+    if shift == 0 {
+        word(index)
+    } else {
+        (word(index) >> shift) | (word(index + 1) << (BITS - shift))
+    }
+}
+
+// Inverse of `word_at`, bits that land outside of the grid are dropped
+fn or_word_at(
+    grid: &mut [usize],
+    origin: (isize, isize),
+    stride: usize,
+    x: isize,
+    y: isize,
+    v: usize,
+) {
+    if v == 0 {
+        return;
+    }
+    let Some(row) = row_start(grid, origin, stride, y) else {
+        return;
+    };
+    let nx = x - origin.0;
+    let index = nx.div_euclid(BITS as isize);
+    let shift = nx.rem_euclid(BITS as isize) as usize;
+    if index >= 0 && (index as usize) < stride {
+        grid[row + index as usize] |= v << shift;
+    }
+    if shift != 0 && index + 1 >= 0 && ((index + 1) as usize) < stride {
+        grid[row + (index + 1) as usize] |= v >> (BITS - shift);
+    }
+}
+
+// Start of each word covering `min_x..=max_x`.  Every grid's words start on a multiple of `BITS`,
+// so the words read from here line up between grids and can be combined without shifting.
+fn word_starts(min_x: isize, max_x: isize) -> impl Iterator<Item = isize> {
+    let bits = BITS as isize;
+    (min_x.div_euclid(bits) * bits..=max_x).step_by(BITS)
+}
+
+// About a third of the cells of `width` x `height` from `min`, picked by xorshift from `seed`
+#[cfg(test)]
+fn random_cells(
+    mut seed: u64,
+    min: (isize, isize),
+    width: isize,
+    height: isize,
+) -> impl Iterator<Item = (isize, isize)> {
+    (0..height)
+        .flat_map(move |y| (0..width).map(move |x| (min.0 + x, min.1 + y)))
+        .filter(move |_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed.is_multiple_of(3)
+        })
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct BitGridConst<
     const MIN_X: isize,
     const MIN_Y: isize,
@@ -11,9 +147,9 @@ impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: u
     for BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
 {
     fn default() -> Self {
-        assert_eq!(WIDTH % Self::BITS, 0);
+        assert_eq!(WIDTH % BITS, 0);
         Self {
-            grid: vec![0; (WIDTH / Self::BITS) * HEIGHT],
+            grid: vec![0; (WIDTH / BITS) * HEIGHT],
         }
     }
 }
@@ -21,31 +157,47 @@ impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: u
 impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize>
     BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
 {
-    const BITS: usize = usize::BITS as usize;
-
     pub fn new() -> Self {
         Self::default()
     }
 
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= MIN_X && y >= MIN_Y && x < MIN_X + WIDTH as isize && y < MIN_Y + HEIGHT as isize
+    }
+
     fn index_bit(&self, x: isize, y: isize) -> (usize, usize) {
+        debug_assert!(self.in_bounds(x, y), "({x}, {y}) is outside of the grid");
         let nx = (x - MIN_X) as usize;
         let ny = (y - MIN_Y) as usize;
         let pos = (ny * WIDTH) + nx;
-        (pos / Self::BITS, 1 << (pos % Self::BITS))
+        (pos / BITS, 1 << (pos % BITS))
+    }
+
+    fn word_at(&self, x: isize, y: isize) -> usize {
+        word_at(&self.grid, (MIN_X, MIN_Y), WIDTH / BITS, x, y)
+    }
+
+    fn or_word_at(&mut self, x: isize, y: isize, v: usize) {
+        or_word_at(&mut self.grid, (MIN_X, MIN_Y), WIDTH / BITS, x, y, v)
     }
 
+    // The whole 3x3 surround must be on the grid, see `get_surround_padded` for edge cells
     pub fn get_surround(&self, x: isize, y: isize) -> u16 {
+        debug_assert!(
+            self.in_bounds(x - 1, y - 1) && self.in_bounds(x + 1, y + 1),
+            "Surround of ({x}, {y}) is outside of the grid"
+        );
         let (index, bit) = self.index_bit(x - 1, y - 1);
         let mut top = self.grid[index] >> bit.trailing_zeros();
-        let mut middle = self.grid[index + (WIDTH / Self::BITS)] >> bit.trailing_zeros();
-        let mut bottom = self.grid[index + (2 * WIDTH / Self::BITS)] >> bit.trailing_zeros();
+        let mut middle = self.grid[index + (WIDTH / BITS)] >> bit.trailing_zeros();
+        let mut bottom = self.grid[index + (2 * WIDTH / BITS)] >> bit.trailing_zeros();
 
-        let extra_bits = Self::BITS - bit.trailing_zeros() as usize;
+        let extra_bits = BITS - bit.trailing_zeros() as usize;
         if extra_bits < 3 {
             let extra_top = self.grid[index + 1] << extra_bits;
-            let extra_middle = self.grid[index + 1 + (WIDTH / Self::BITS)] << extra_bits;
+            let extra_middle = self.grid[index + 1 + (WIDTH / BITS)] << extra_bits;
 
-            let extra_bottom = self.grid[index + 1 + (2 * WIDTH / Self::BITS)] << extra_bits;
+            let extra_bottom = self.grid[index + 1 + (2 * WIDTH / BITS)] << extra_bits;
             top |= extra_top;
             middle |= extra_middle;
             bottom |= extra_bottom;
@@ -54,6 +206,12 @@ impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: u
         (((top & 0b111) << 6) | ((middle & 0b111) << 3) | (bottom & 0b111)) as u16
     }
 
+    // Same as `get_surround`, with cells off the grid read as clear
+    pub fn get_surround_padded(&self, x: isize, y: isize) -> u16 {
+        let row = |y| (self.word_at(x - 1, y) & 0b111) as u16;
+        (row(y - 1) << 6) | (row(y) << 3) | row(y + 1)
+    }
+
     pub fn set_bit(&mut self, x: isize, y: isize) {
         let (index, bit) = self.index_bit(x, y);
         self.grid[index] |= bit;
@@ -69,18 +227,57 @@ impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: u
         self.grid[index] & bit != 0
     }
 
+    // None if (x, y) is outside of the grid, otherwise if the bit was already set
+    pub fn try_set(&mut self, x: isize, y: isize) -> Option<bool> {
+        if !self.in_bounds(x, y) {
+            return None;
+        }
+        let prev = self.bit_is_set(x, y);
+        self.set_bit(x, y);
+        Some(prev)
+    }
+
+    pub fn try_get(&self, x: isize, y: isize) -> Option<bool> {
+        if self.in_bounds(x, y) {
+            Some(self.bit_is_set(x, y))
+        } else {
+            None
+        }
+    }
+
     pub fn clear(&mut self) {
         self.grid.iter_mut().for_each(|v| *v = 0);
     }
+
+    pub fn iter_set(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        SetBits::new(&self.grid)
+            .map(|i| (MIN_X + (i % WIDTH) as isize, MIN_Y + (i / WIDTH) as isize))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point2D<isize>> + '_ {
+        self.iter_set().map(|(x, y)| Point2D::new(x, y))
+    }
+}
+
+impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize> Render
+    for BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+{
+    fn render(&self) -> Frame {
+        Frame::from_fn(
+            (MIN_X, MIN_Y),
+            (MIN_X + WIDTH as isize - 1, MIN_Y + HEIGHT as isize - 1),
+            |x, y| if self.bit_is_set(x, y) { '#' } else { '.' },
+        )
+    }
 }
 
+#[derive(Clone)]
 pub struct BitGrid<const BX: usize = 10, const BY: usize = 10> {
     grid: Vec<usize>,
     min: (isize, isize),
     max: (isize, isize),
     width: usize,
-    min_set: (isize, isize),
-    max_set: (isize, isize),
+    set: SetBounds,
 }
 
 impl<const BX: usize, const BY: usize> Default for BitGrid<BX, BY> {
@@ -90,15 +287,12 @@ impl<const BX: usize, const BY: usize> Default for BitGrid<BX, BY> {
             min: (isize::MAX, isize::MAX),
             max: (isize::MIN, isize::MIN),
             width: 0,
-            min_set: (isize::MAX, isize::MAX),
-            max_set: (isize::MIN, isize::MIN),
+            set: SetBounds::default(),
         }
     }
 }
 
 impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
-    const BITS: usize = usize::BITS as usize;
-
     pub fn new() -> Self {
         Self::default()
     }
@@ -117,16 +311,16 @@ impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
         let nx = (x - min.0) as usize;
         let ny = (y - min.1) as usize;
         let pos = (ny * width) + nx;
-        (pos / Self::BITS, 1 << (pos % Self::BITS))
+        (pos / BITS, 1 << (pos % BITS))
     }
 
     fn resize(&mut self, min: (isize, isize), max: (isize, isize)) {
         let new_min = ((min.0 >> 6) << 6, min.1);
-        let new_max = (((max.0 >> 6) << 6) + usize::BITS as isize - 1, max.1);
+        let new_max = (((max.0 >> 6) << 6) + BITS as isize - 1, max.1);
 
         let new_width = (new_max.0 - new_min.0 + 1) as usize;
         let new_height = (new_max.1 - new_min.1 + 1) as usize;
-        let mut new_grid = vec![0; (new_width / Self::BITS) * new_height];
+        let mut new_grid = vec![0; (new_width / BITS) * new_height];
         debug_assert!(
             (new_min != self.min || new_max != self.max)
                 && new_min.0 <= self.min.0
@@ -145,8 +339,8 @@ impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
             } else {
                 // Need to copy each row individuallyA
                 let mut old_index = 0;
-                let old_stride = self.width / usize::BITS as usize;
-                let new_stride = new_width / usize::BITS as usize;
+                let old_stride = self.width / BITS;
+                let new_stride = new_width / BITS;
                 for _ in self.min.1..=self.max.1 {
                     new_grid[index..index + old_stride]
                         .copy_from_slice(&self.grid[old_index..old_index + old_stride]);
@@ -163,33 +357,28 @@ impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
     }
 
     pub fn get_surround(&mut self, x: isize, y: isize) -> u16 {
-        self.min_set.0 = self.min_set.0.min(x - 1);
-        self.min_set.1 = self.min_set.1.min(y - 1);
-        self.max_set.0 = self.max_set.0.max(x + 1);
-        self.max_set.1 = self.max_set.1.max(y + 1);
-
-        if self.min_set.0 < self.min.0
-            || self.max_set.0 > self.max.0
-            || self.min_set.1 < self.min.1
-            || self.max_set.1 > self.max.1
-        {
+        self.set.include(x - 1, y - 1);
+        self.set.include(x + 1, y + 1);
+
+        let SetBounds { min, max } = self.set;
+        if min.0 < self.min.0 || max.0 > self.max.0 || min.1 < self.min.1 || max.1 > self.max.1 {
             self.resize(
-                (self.min_set.0 - BX as isize, self.min_set.1 - BY as isize),
-                (self.max_set.0 + BX as isize, self.max_set.1 + BY as isize),
+                (min.0 - BX as isize, min.1 - BY as isize),
+                (max.0 + BX as isize, max.1 + BY as isize),
             );
         }
 
         let (index, bit) = self.index_bit(x - 1, y - 1);
         let mut top = self.grid[index] >> bit.trailing_zeros();
-        let mut middle = self.grid[index + (self.width / Self::BITS)] >> bit.trailing_zeros();
-        let mut bottom = self.grid[index + (2 * self.width / Self::BITS)] >> bit.trailing_zeros();
+        let mut middle = self.grid[index + (self.width / BITS)] >> bit.trailing_zeros();
+        let mut bottom = self.grid[index + (2 * self.width / BITS)] >> bit.trailing_zeros();
 
-        let extra_bits = Self::BITS - bit.trailing_zeros() as usize;
+        let extra_bits = BITS - bit.trailing_zeros() as usize;
         if extra_bits < 3 {
             let extra_top = self.grid[index + 1] << extra_bits;
-            let extra_middle = self.grid[index + 1 + (self.width / Self::BITS)] << extra_bits;
+            let extra_middle = self.grid[index + 1 + (self.width / BITS)] << extra_bits;
 
-            let extra_bottom = self.grid[index + 1 + (2 * self.width / Self::BITS)] << extra_bits;
+            let extra_bottom = self.grid[index + 1 + (2 * self.width / BITS)] << extra_bits;
             top |= extra_top;
             middle |= extra_middle;
             bottom |= extra_bottom;
@@ -199,19 +388,13 @@ impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
     }
 
     pub fn set_bit(&mut self, x: isize, y: isize) {
-        self.min_set.0 = self.min_set.0.min(x);
-        self.min_set.1 = self.min_set.1.min(y);
-        self.max_set.0 = self.max_set.0.max(x);
-        self.max_set.1 = self.max_set.1.max(y);
-
-        if self.min_set.0 < self.min.0
-            || self.max_set.0 > self.max.0
-            || self.min_set.1 < self.min.1
-            || self.max_set.1 > self.max.1
-        {
+        self.set.include(x, y);
+
+        let SetBounds { min, max } = self.set;
+        if min.0 < self.min.0 || max.0 > self.max.0 || min.1 < self.min.1 || max.1 > self.max.1 {
             self.resize(
-                (self.min_set.0 - BX as isize, self.min_set.1 - BY as isize),
-                (self.max_set.0 + BX as isize, self.max_set.1 + BY as isize),
+                (min.0 - BX as isize, min.1 - BY as isize),
+                (max.0 + BX as isize, max.1 + BY as isize),
             );
         }
 
@@ -242,6 +425,45 @@ impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
     pub fn count_set(&self) -> usize {
         self.grid.iter().fold(0, |c, v| c + v.count_ones() as usize)
     }
+
+    pub fn iter_set(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        let (min, width) = (self.min, self.width);
+        SetBits::new(&self.grid)
+            .map(move |i| (min.0 + (i % width) as isize, min.1 + (i / width) as isize))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point2D<isize>> + '_ {
+        self.iter_set().map(|(x, y)| Point2D::new(x, y))
+    }
+
+    // An empty grid already sized to hold `min..=max` as its set bounds
+    fn with_set_bounds(min: (isize, isize), max: (isize, isize)) -> Self {
+        let mut grid = Self::new();
+        grid.resize(
+            (min.0 - BX as isize, min.1 - BY as isize),
+            (max.0 + BX as isize, max.1 + BY as isize),
+        );
+        grid.set = SetBounds { min, max };
+        grid
+    }
+
+    fn word_at(&self, x: isize, y: isize) -> usize {
+        word_at(&self.grid, self.min, self.width / BITS, x, y)
+    }
+
+    fn or_word_at(&mut self, x: isize, y: isize, v: usize) {
+        or_word_at(&mut self.grid, self.min, self.width / BITS, x, y, v)
+    }
+
+    pub fn set_bounds(&self) -> Option<Bounds> {
+        self.set.get()
+    }
+}
+
+impl<const BX: usize, const BY: usize> Render for BitGrid<BX, BY> {
+    fn render(&self) -> Frame {
+        self.display().render()
+    }
 }
 
 mod test {
@@ -272,16 +494,16 @@ mod test {
             grid.grid.len(),
             grid.min,
             grid.max,
-            grid.min_set,
-            grid.max_set,
+            grid.set.min,
+            grid.set.max,
             grid.width
         );
         assert_eq!(grid.grid.len(), 21);
         assert_eq!(grid.width, 64);
         assert_eq!(grid.min, (64, 90));
         assert_eq!(grid.max, (127, 110));
-        assert_eq!(grid.min_set, (100, 100));
-        assert_eq!(grid.max_set, (100, 100));
+        assert_eq!(grid.set.min, (100, 100));
+        assert_eq!(grid.set.max, (100, 100));
 
         println!("Check for resize in the -y dir");
         for i in 1..=10 {
@@ -292,8 +514,8 @@ mod test {
         assert_eq!(grid.width, 64);
         assert_eq!(grid.min, (64, 90));
         assert_eq!(grid.max, (127, 110));
-        assert_eq!(grid.min_set, (100, 90));
-        assert_eq!(grid.max_set, (100, 100));
+        assert_eq!(grid.set.min, (100, 90));
+        assert_eq!(grid.set.max, (100, 100));
 
         // One more will resize
         grid.set_bit(100, 89);
@@ -302,8 +524,8 @@ mod test {
         assert_eq!(grid.width, 64);
         assert_eq!(grid.min, (64, 79));
         assert_eq!(grid.max, (127, 110));
-        assert_eq!(grid.min_set, (100, 89));
-        assert_eq!(grid.max_set, (100, 100));
+        assert_eq!(grid.set.min, (100, 89));
+        assert_eq!(grid.set.max, (100, 100));
 
         println!("Check for resize in the +y dir");
         for i in 1..=10 {
@@ -314,8 +536,8 @@ mod test {
         assert_eq!(grid.width, 64);
         assert_eq!(grid.min, (64, 79));
         assert_eq!(grid.max, (127, 110));
-        assert_eq!(grid.min_set, (100, 89));
-        assert_eq!(grid.max_set, (100, 110));
+        assert_eq!(grid.set.min, (100, 89));
+        assert_eq!(grid.set.max, (100, 110));
 
         // One more will resize
         grid.set_bit(100, 111);
@@ -326,16 +548,16 @@ mod test {
             grid.grid.len(),
             grid.min,
             grid.max,
-            grid.min_set,
-            grid.max_set,
+            grid.set.min,
+            grid.set.max,
             grid.width
         );
         assert_eq!(grid.grid.len(), 43);
         assert_eq!(grid.width, 64);
         assert_eq!(grid.min, (64, 79));
         assert_eq!(grid.max, (127, 121));
-        assert_eq!(grid.min_set, (100, 89));
-        assert_eq!(grid.max_set, (100, 111));
+        assert_eq!(grid.set.min, (100, 89));
+        assert_eq!(grid.set.max, (100, 111));
 
         // Should still not resize
         grid.clear_bit(0, 100);
@@ -343,8 +565,8 @@ mod test {
         assert_eq!(grid.width, 64);
         assert_eq!(grid.min, (64, 79));
         assert_eq!(grid.max, (127, 121));
-        assert_eq!(grid.min_set, (100, 89));
-        assert_eq!(grid.max_set, (100, 111));
+        assert_eq!(grid.set.min, (100, 89));
+        assert_eq!(grid.set.max, (100, 111));
 
         // This will resize in the -x
         grid.set_bit(0, 100);
@@ -354,16 +576,16 @@ mod test {
             grid.grid.len(),
             grid.min,
             grid.max,
-            grid.min_set,
-            grid.max_set,
+            grid.set.min,
+            grid.set.max,
             grid.width
         );
         assert_eq!(grid.grid.len(), 129);
         assert_eq!(grid.width, 192);
         assert_eq!(grid.min, (-64, 79));
         assert_eq!(grid.max, (127, 121));
-        assert_eq!(grid.min_set, (0, 89));
-        assert_eq!(grid.max_set, (100, 111));
+        assert_eq!(grid.set.min, (0, 89));
+        assert_eq!(grid.set.max, (100, 111));
 
         // This will resize in the +x
         grid.set_bit(255, 100);
@@ -373,16 +595,16 @@ mod test {
             grid.grid.len(),
             grid.min,
             grid.max,
-            grid.min_set,
-            grid.max_set,
+            grid.set.min,
+            grid.set.max,
             grid.width
         );
         assert_eq!(grid.grid.len(), 258);
         assert_eq!(grid.width, 384);
         assert_eq!(grid.min, (-64, 79));
         assert_eq!(grid.max, (319, 121));
-        assert_eq!(grid.min_set, (0, 89));
-        assert_eq!(grid.max_set, (255, 111));
+        assert_eq!(grid.set.min, (0, 89));
+        assert_eq!(grid.set.max, (255, 111));
 
         // Scan to see if the set_bits and grid agree
         for y in grid.min.1..=grid.max.1 {
@@ -403,8 +625,66 @@ mod test {
         assert_eq!(grid.width, 384);
         assert_eq!(grid.min, (-64, 79));
         assert_eq!(grid.max, (319, 121));
-        assert_eq!(grid.min_set, (0, 89));
-        assert_eq!(grid.max_set, (255, 111));
+        assert_eq!(grid.set.min, (0, 89));
+        assert_eq!(grid.set.max, (255, 111));
+    }
+
+    #[test]
+    fn iter_set() {
+        let mut grid: super::BitGrid = super::BitGrid::new();
+        assert_eq!(grid.iter_set().count(), 0);
+        let bits = [(-70, -3), (5, -3), (64, 0), (-1, 12)];
+        for (x, y) in bits {
+            grid.set_bit(x, y);
+        }
+        grid.set_bit(0, 0);
+        grid.clear_bit(0, 0);
+        assert_eq!(grid.iter_set().collect::<Vec<_>>(), bits);
+        assert_eq!(grid.points().nth(2), Some(crate::Point2D::new(64, 0)));
+
+        let mut grid: super::BitGridConst<-64, -1, 128, 3> = super::BitGridConst::new();
+        let bits = [(63, -1), (-64, 0), (0, 0), (-1, 1)];
+        for (x, y) in bits {
+            grid.set_bit(x, y);
+        }
+        assert_eq!(grid.iter_set().collect::<Vec<_>>(), bits);
+        assert_eq!(grid.points().count(), 4);
+    }
+
+    #[test]
+    fn bit_grid_const_edges() {
+        let mut grid: super::BitGridConst<-64, -1, 128, 3> = super::BitGridConst::new();
+        for (x, y) in [(-64, -1), (63, -1), (-64, 1), (63, 1)] {
+            assert_eq!(grid.try_set(x, y), Some(false));
+            assert_eq!(grid.try_set(x, y), Some(true));
+            assert_eq!(grid.try_get(x, y), Some(true));
+        }
+        for (x, y) in [(-65, 0), (64, 0), (0, -2), (0, 2), (isize::MIN, isize::MAX)] {
+            assert!(!grid.in_bounds(x, y));
+            assert_eq!(grid.try_set(x, y), None);
+            assert_eq!(grid.try_get(x, y), None);
+        }
+        assert_eq!(grid.iter_set().count(), 4);
+
+        // Corners only see the one set corner
+        assert_eq!(grid.get_surround_padded(-64, -1), 0b000_010_000);
+        assert_eq!(grid.get_surround_padded(63, 1), 0b000_010_000);
+        assert_eq!(grid.get_surround_padded(-65, -2), 0b000_000_100);
+        assert_eq!(grid.get_surround_padded(64, 2), 0b001_000_000);
+        assert_eq!(grid.get_surround_padded(-64, 0), 0b010_000_010);
+        grid.set_bit(-1, 0);
+        grid.set_bit(0, 0);
+        assert_eq!(grid.get_surround_padded(0, 0), grid.get_surround(0, 0));
+        assert_eq!(grid.get_surround_padded(-1, 0), 0b000_110_000);
+        assert_eq!(grid.get_surround_padded(0, 0), 0b000_011_000);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "outside of the grid")]
+    fn bit_grid_const_out_of_bounds() {
+        let grid: super::BitGridConst<0, 0, 64, 2> = super::BitGridConst::new();
+        grid.get_surround(63, 0);
     }
 
     #[test]
@@ -0,0 +1,212 @@
+use super::{BitGrid, BitGridConst, Bounds, BITS};
+use crate::{Frame, Render};
+use std::fmt;
+
+// Formats the set bounds of a grid, one line per row without a trailing new line so it can be
+// used with `println!`
+pub struct BitGridDisplay<'a> {
+    bounds: Option<Bounds>,
+    bit_is_set: Box<dyn Fn(isize, isize) -> bool + 'a>,
+    on: char,
+    off: char,
+    axes: bool,
+}
+
+impl<'a> BitGridDisplay<'a> {
+    pub(super) fn new(
+        bounds: Option<Bounds>,
+        bit_is_set: impl Fn(isize, isize) -> bool + 'a,
+    ) -> Self {
+        Self {
+            bounds,
+            bit_is_set: Box::new(bit_is_set),
+            on: '#',
+            off: '.',
+            axes: false,
+        }
+    }
+
+    pub fn on(mut self, on: char) -> Self {
+        self.on = on;
+        self
+    }
+
+    pub fn off(mut self, off: char) -> Self {
+        self.off = off;
+        self
+    }
+
+    // Label columns with their x coordinate written downwards, and rows with their y coordinate
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    fn cell(&self, x: isize, y: isize) -> char {
+        if (self.bit_is_set)(x, y) {
+            self.on
+        } else {
+            self.off
+        }
+    }
+}
+
+impl fmt::Display for BitGridDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+
+        let y_width = if self.axes {
+            min.1.to_string().len().max(max.1.to_string().len())
+        } else {
+            0
+        };
+        if self.axes {
+            let labels: Vec<String> = (min.0..=max.0).map(|x| x.to_string()).collect();
+            let x_height = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+            for i in 0..x_height {
+                write!(f, "{:y_width$} ", "")?;
+                for label in labels.iter() {
+                    let pad = x_height - label.len();
+                    let c = if i < pad {
+                        ' '
+                    } else {
+                        label.as_bytes()[i - pad] as char
+                    };
+                    write!(f, "{c}")?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        for y in min.1..=max.1 {
+            if self.axes {
+                write!(f, "{y:>y_width$} ")?;
+            }
+            for x in min.0..=max.0 {
+                write!(f, "{}", self.cell(x, y))?;
+            }
+            if y != max.1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// The same cells as `Display`, without the axes
+impl Render for BitGridDisplay<'_> {
+    fn render(&self) -> Frame {
+        match self.bounds {
+            Some((min, max)) => Frame::from_fn(min, max, |x, y| self.cell(x, y)),
+            None => Frame::new(),
+        }
+    }
+}
+
+impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize>
+    BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+{
+    // Bounds of the bits that are set, found by scanning every word
+    pub fn set_bounds(&self) -> Option<Bounds> {
+        let stride = WIDTH / BITS;
+        let mut bounds: Option<Bounds> = None;
+        for (index, &word) in self.grid.iter().enumerate() {
+            if word == 0 {
+                continue;
+            }
+            let y = MIN_Y + (index / stride) as isize;
+            let x = MIN_X + ((index % stride) * BITS) as isize;
+            let lo = x + word.trailing_zeros() as isize;
+            let hi = x + (BITS - 1 - word.leading_zeros() as usize) as isize;
+            bounds = Some(match bounds {
+                None => ((lo, y), (hi, y)),
+                Some((min, max)) => ((min.0.min(lo), min.1), (max.0.max(hi), y)),
+            });
+        }
+        bounds
+    }
+
+    pub fn display(&self) -> BitGridDisplay<'_> {
+        BitGridDisplay::new(self.set_bounds(), |x, y| self.bit_is_set(x, y))
+    }
+}
+
+impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize> fmt::Display
+    for BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
+    // Cleared bits don't widen the box, the same as `BitGridConst`
+    pub fn display(&self) -> BitGridDisplay<'_> {
+        BitGridDisplay::new(self.tight_bounds(), |x, y| self.bit_is_set(x, y))
+    }
+}
+
+impl<const BX: usize, const BY: usize> fmt::Display for BitGrid<BX, BY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Level, Output, SparseBitGrid, YearDayPart};
+
+    #[test]
+    fn display() {
+        let mut grid: BitGrid = BitGrid::new();
+        assert_eq!(grid.to_string(), "");
+        grid.set_bit(-1, 9);
+        grid.set_bit(1, 10);
+        assert_eq!(grid.to_string(), "#..\n..#");
+        assert_eq!(grid.display().on('█').off(' ').to_string(), "█  \n  █");
+        assert_eq!(
+            grid.display().axes(true).to_string(),
+            "   -  \n   101\n 9 #..\n10 ..#"
+        );
+        grid.set_bit(5, 20);
+        grid.clear_bit(5, 20);
+        assert_eq!(grid.to_string(), "#..\n..#");
+        let mut sparse = SparseBitGrid::new();
+        for (x, y) in [(-1, 9), (1, 10), (500, 500)] {
+            sparse.set_bit(x, y);
+        }
+        sparse.clear_bit(500, 500);
+        assert_eq!(sparse.to_string(), "#..\n..#");
+
+        let mut grid: BitGridConst<-64, -2, 192, 5> = BitGridConst::new();
+        assert_eq!(grid.set_bounds(), None);
+        grid.set_bit(-1, 0);
+        grid.set_bit(64, -1);
+        grid.set_bit(0, 1);
+        assert_eq!(grid.set_bounds(), Some(((-1, -1), (64, 1))));
+        let s = grid.to_string();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|l| l.len() == 66));
+        assert!(lines[0].ends_with(".#"));
+        assert!(lines[1].starts_with("#."));
+        assert!(lines[2].starts_with(".#."));
+    }
+
+    #[test]
+    fn println() {
+        let mut grid: BitGrid = BitGrid::new();
+        grid.set_bit(0, 0);
+        grid.set_bit(1, 1);
+
+        let mut output = Output::new();
+        output.capture();
+        output.start_run(YearDayPart::new(2022, 17, 1));
+        output.write_level(Level::Info, format_args!("{grid}\n"));
+        let capture = output.get_capture();
+        assert_eq!(capture.unwrap(), "2022-17 Part 1: #.\n2022-17 Part 1: .#\n");
+    }
+}
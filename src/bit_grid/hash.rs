@@ -0,0 +1,197 @@
+use super::{word_starts, BitGrid, BitGridConst, Bounds, SetBounds, SparseBitGrid, BITS};
+use rustc_hash::FxHasher;
+use std::hash::{Hash, Hasher};
+
+// Grids are equal when the same cells are set, however much space they have allocated and whatever
+// bits have been set and cleared since.  Only the bounding box of the set cells is compared.
+impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
+    // Bounding box of the bits that are set now
+    pub fn tight_bounds(&self) -> Option<Bounds> {
+        let (min, max) = self.set_bounds()?;
+        let mut bounds: Option<Bounds> = None;
+        for y in min.1..=max.1 {
+            for x in word_starts(min.0, max.0) {
+                let word = self.word_at(x, y);
+                if word == 0 {
+                    continue;
+                }
+                let lo = x + word.trailing_zeros() as isize;
+                let hi = x + BITS as isize - 1 - word.leading_zeros() as isize;
+                bounds = Some(match bounds {
+                    None => ((lo, y), (hi, y)),
+                    Some((min, max)) => ((min.0.min(lo), min.1), (max.0.max(hi), y)),
+                });
+            }
+        }
+        bounds
+    }
+
+    // Consistent with `Eq`, equal grids have equal fingerprints
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<const BX: usize, const BY: usize> PartialEq for BitGrid<BX, BY> {
+    fn eq(&self, other: &Self) -> bool {
+        let bounds = self.tight_bounds();
+        if bounds != other.tight_bounds() {
+            return false;
+        }
+        let Some((min, max)) = bounds else {
+            return true;
+        };
+        (min.1..=max.1)
+            .all(|y| word_starts(min.0, max.0).all(|x| self.word_at(x, y) == other.word_at(x, y)))
+    }
+}
+
+impl<const BX: usize, const BY: usize> Eq for BitGrid<BX, BY> {}
+
+impl<const BX: usize, const BY: usize> Hash for BitGrid<BX, BY> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let bounds = self.tight_bounds();
+        bounds.hash(state);
+        if let Some((min, max)) = bounds {
+            for y in min.1..=max.1 {
+                for x in word_starts(min.0, max.0) {
+                    self.word_at(x, y).hash(state);
+                }
+            }
+        }
+    }
+}
+
+impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize>
+    BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+{
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// Empty tiles are never kept, so equal grids hold the same tiles
+impl SparseBitGrid {
+    // Bounding box of the bits that are set now
+    pub fn tight_bounds(&self) -> Option<Bounds> {
+        let mut set = SetBounds::default();
+        for (x, y, word) in self.words() {
+            set.include(x + word.trailing_zeros() as isize, y);
+            set.include(x + BITS as isize - 1 - word.leading_zeros() as isize, y);
+        }
+        set.get()
+    }
+
+    // Consistent with `Eq`, equal grids have equal fingerprints
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl PartialEq for SparseBitGrid {
+    fn eq(&self, other: &Self) -> bool {
+        self.tiles == other.tiles
+    }
+}
+
+impl Eq for SparseBitGrid {}
+
+impl Hash for SparseBitGrid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut tiles: Vec<_> = self.tiles.iter().collect();
+        tiles.sort_unstable_by_key(|&(&key, _)| key);
+        tiles.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HashMap, HashSet, LifeRule};
+
+    #[test]
+    fn eq() {
+        let mut a: BitGrid = BitGrid::new();
+        let mut b: BitGrid = BitGrid::new();
+        assert!(a == b);
+        assert_eq!(a.tight_bounds(), None);
+
+        // Different allocations and stale set bounds
+        b.set_bit(-500, 300);
+        b.clear_bit(-500, 300);
+        assert!(a == b);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        for (x, y) in [(3, 4), (70, 5), (-1, 4)] {
+            a.set_bit(x, y);
+            b.set_bit(x, y);
+        }
+        assert_eq!(b.tight_bounds(), Some(((-1, 4), (70, 5))));
+        assert!(a == b);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+
+        b.set_bit(71, 5);
+        assert!(a != b);
+        b.clear_bit(71, 5);
+        b.set_bit(4, 4);
+        assert!(a != b);
+        assert_ne!(a.fingerprint(), b.fingerprint());
+
+        // Moving a grid changes it
+        assert!(a != a.shifted(1, 0));
+
+        let mut c: BitGridConst<-64, 0, 128, 2> = BitGridConst::new();
+        let d = c.clone();
+        c.set_bit(-64, 1);
+        assert!(c != d);
+        c.clear_bit(-64, 1);
+        assert!(c == d);
+        assert_eq!(c.fingerprint(), d.fingerprint());
+
+        let mut e = SparseBitGrid::new();
+        let mut f = SparseBitGrid::new();
+        f.set_bit(-500, 300);
+        f.clear_bit(-500, 300);
+        assert!(e == f);
+        assert_eq!(f.tight_bounds(), None);
+        for (x, y) in [(3, 4), (70, 5), (-1, 4), (isize::MAX / 2, 0)] {
+            e.set_bit(x, y);
+            f.set_bit(x, y);
+        }
+        assert_eq!(f.tight_bounds(), Some(((-1, 0), (isize::MAX / 2, 5))));
+        assert!(e == f);
+        assert_eq!(e.fingerprint(), f.fingerprint());
+        f.set_bit(4, 4);
+        assert!(e != f);
+        assert_ne!(e.fingerprint(), f.fingerprint());
+        assert!(e != e.shifted(1, 0));
+    }
+
+    #[test]
+    fn cycle_detection() {
+        // A blinker next to a block repeats every 2 generations
+        let mut grid: BitGrid = BitGrid::new();
+        for (x, y) in [(0, 0), (1, 0), (2, 0), (10, 0), (11, 0), (10, 1), (11, 1)] {
+            grid.set_bit(x, y);
+        }
+        let rule = LifeRule::conway();
+        let mut seen = HashMap::default();
+        let mut generation = 0;
+        let start = loop {
+            if let Some(start) = seen.insert(grid.clone(), generation) {
+                break start;
+            }
+            grid = grid.step(&rule);
+            generation += 1;
+        };
+        assert_eq!((start, generation), (0, 2));
+
+        let fingerprints: HashSet<u64> = seen.keys().map(|grid| grid.fingerprint()).collect();
+        assert_eq!(fingerprints.len(), 2);
+    }
+}
@@ -0,0 +1,427 @@
+use super::{word_starts, BitGrid, BitGridConst, SetBounds, SparseBitGrid, BITS};
+use crate::{Error, HashMap, HashSet};
+use std::str::FromStr;
+
+// Next state of a cell from its surround, as returned by `get_surround`.  Steps work on whole words,
+// with the nine neighbourhood bits of 64 cells held in nine words.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifeRule {
+    kind: Kind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    // Bit n is set if a cell with n live neighbours is born or survives
+    Counts {
+        birth: u16,
+        survive: u16,
+    },
+    // Decision diagram over the surround bits.  Nodes are (bit, clear, set) and refer to earlier
+    // nodes, with 0 and 1 being the constants.
+    Table {
+        nodes: Vec<(usize, usize, usize)>,
+        root: usize,
+    },
+}
+
+impl LifeRule {
+    pub fn new(birth: &[usize], survive: &[usize]) -> Self {
+        let mask = |counts: &[usize]| {
+            counts.iter().fold(0, |mask, &n| {
+                assert!(n <= 8, "{n} neighbours");
+                mask | (1 << n)
+            })
+        };
+        Self {
+            kind: Kind::Counts {
+                birth: mask(birth),
+                survive: mask(survive),
+            },
+        }
+    }
+
+    // B3/S23
+    pub fn conway() -> Self {
+        Self::new(&[3], &[2, 3])
+    }
+
+    // Any rule, `f` is called once for each of the 512 surrounds
+    pub fn from_table(f: impl Fn(u16) -> bool) -> Self {
+        let table: Vec<bool> = (0..512).map(f).collect();
+        let mut nodes = Vec::new();
+        let root = Self::build(&table, &mut nodes, &mut HashMap::default());
+        Self {
+            kind: Kind::Table { nodes, root },
+        }
+    }
+
+    fn build(
+        table: &[bool],
+        nodes: &mut Vec<(usize, usize, usize)>,
+        seen: &mut HashMap<(usize, usize, usize), usize>,
+    ) -> usize {
+        if table.len() == 1 {
+            return table[0] as usize;
+        }
+        let half = table.len() / 2;
+        let clear = Self::build(&table[..half], nodes, seen);
+        let set = Self::build(&table[half..], nodes, seen);
+        if clear == set {
+            return clear;
+        }
+        let node = (half.trailing_zeros() as usize, clear, set);
+        *seen.entry(node).or_insert_with(|| {
+            nodes.push(node);
+            nodes.len() + 1
+        })
+    }
+
+    // Per cell version of the rule
+    pub fn next(&self, surround: u16) -> bool {
+        match &self.kind {
+            Kind::Counts { birth, survive } => {
+                let neighbors = (surround & !0b10000).count_ones();
+                let mask = if surround & 0b10000 != 0 {
+                    survive
+                } else {
+                    birth
+                };
+                mask & (1 << neighbors) != 0
+            }
+            Kind::Table { nodes, root } => {
+                let mut node = *root;
+                while node > 1 {
+                    let (bit, clear, set) = nodes[node - 2];
+                    node = if surround & (1 << bit) != 0 {
+                        set
+                    } else {
+                        clear
+                    };
+                }
+                node == 1
+            }
+        }
+    }
+
+    // Next state of 64 cells, `surround[i]` holds bit i of each cell's surround.  `values` is
+    // scratch space for the decision diagram.
+    fn next_word(&self, surround: &[usize; 9], values: &mut Vec<usize>) -> usize {
+        match &self.kind {
+            Kind::Counts { birth, survive } => {
+                let count = count_neighbors(surround);
+                let (mut born, mut survives) = (0, 0);
+                for n in 0..9 {
+                    if (birth | survive) & (1 << n) == 0 {
+                        continue;
+                    }
+                    let matches = count
+                        .iter()
+                        .enumerate()
+                        .fold(usize::MAX, |matches, (i, &bit)| {
+                            matches & if n & (1 << i) != 0 { bit } else { !bit }
+                        });
+                    if birth & (1 << n) != 0 {
+                        born |= matches;
+                    }
+                    if survive & (1 << n) != 0 {
+                        survives |= matches;
+                    }
+                }
+                (surround[4] & survives) | (!surround[4] & born)
+            }
+            Kind::Table { nodes, root } => {
+                values.clear();
+                values.extend([0, usize::MAX]);
+                for &(bit, clear, set) in nodes.iter() {
+                    let v = surround[bit];
+                    values.push((v & values[set]) | (!v & values[clear]));
+                }
+                values[*root]
+            }
+        }
+    }
+}
+
+fn full_add(a: usize, b: usize, c: usize) -> (usize, usize) {
+    (a ^ b ^ c, (a & b) | (c & (a ^ b)))
+}
+
+// Bit sliced count of the 8 neighbours, least significant bit first
+fn count_neighbors(surround: &[usize; 9]) -> [usize; 4] {
+    let (s0, c0) = full_add(surround[0], surround[1], surround[2]);
+    let (s1, c1) = full_add(surround[3], surround[5], surround[6]);
+    let (s2, c2) = full_add(surround[7], surround[8], 0);
+    let (ones, c3) = full_add(s0, s1, s2);
+    let (t0, d0) = full_add(c0, c1, c2);
+    let (twos, d1) = full_add(t0, c3, 0);
+    [ones, twos, d0 ^ d1, d0 & d1]
+}
+
+// The surround words of the 64 cells starting at (x, y), in `get_surround` order
+fn surround_words(word_at: impl Fn(isize, isize) -> usize, x: isize, y: isize) -> [usize; 9] {
+    let mut surround = [0; 9];
+    for (row, dy) in [1, 0, -1].into_iter().enumerate() {
+        for (column, dx) in [-1, 0, 1].into_iter().enumerate() {
+            surround[row * 3 + column] = word_at(x + dx, y + dy);
+        }
+    }
+    surround
+}
+
+// "B3/S23"
+impl FromStr for LifeRule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidInput(format!("Invalid life rule {s:?}"));
+        let counts = |part: &str, prefix: char| -> Result<Vec<usize>, Error> {
+            let digits = part
+                .strip_prefix(prefix)
+                .or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()))
+                .ok_or_else(invalid)?;
+            digits
+                .chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(n) if n <= 8 => Ok(n as usize),
+                    _ => Err(invalid()),
+                })
+                .collect()
+        };
+        let (birth, survive) = s.trim().split_once('/').ok_or_else(invalid)?;
+        Ok(Self::new(&counts(birth, 'B')?, &counts(survive, 'S')?))
+    }
+}
+
+impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
+    // Cells more than one away from the set bounds stay clear, even for rules with B0
+    pub fn step(&self, rule: &LifeRule) -> Self {
+        let Some((min, max)) = self.set_bounds() else {
+            return Self::new();
+        };
+        let (min, max) = ((min.0 - 1, min.1 - 1), (max.0 + 1, max.1 + 1));
+        let mut next = Self::with_set_bounds(min, max);
+        let mut set = SetBounds::default();
+
+        let mut values = Vec::new();
+        for y in min.1..=max.1 {
+            for x in word_starts(min.0, max.0) {
+                let surround = surround_words(|x, y| self.word_at(x, y), x, y);
+                let mut word = rule.next_word(&surround, &mut values);
+                if min.0 > x {
+                    word &= usize::MAX << (min.0 - x);
+                }
+                if max.0 < x + BITS as isize - 1 {
+                    word &= usize::MAX >> (x + BITS as isize - 1 - max.0);
+                }
+                if word == 0 {
+                    continue;
+                }
+                let lo = x + word.trailing_zeros() as isize;
+                let hi = x + BITS as isize - 1 - word.leading_zeros() as isize;
+                set.include(lo, y);
+                set.include(hi, y);
+                next.or_word_at(x, y, word);
+            }
+        }
+
+        if set.get().is_none() {
+            return Self::new();
+        }
+        next.set = set;
+        next
+    }
+}
+
+impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize>
+    BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+{
+    // Cells outside of the grid are clear
+    pub fn step(&self, rule: &LifeRule) -> Self {
+        let mut next = Self::new();
+        let mut values = Vec::new();
+        let stride = WIDTH / BITS;
+        for (index, word) in next.grid.iter_mut().enumerate() {
+            let x = MIN_X + ((index % stride) * BITS) as isize;
+            let y = MIN_Y + (index / stride) as isize;
+            let surround = surround_words(|x, y| self.word_at(x, y), x, y);
+            *word = rule.next_word(&surround, &mut values);
+        }
+        next
+    }
+}
+
+impl SparseBitGrid {
+    // Only the words touching a stored tile are stepped, and cells more than one away from the set
+    // bounds stay clear, so rules with B0 don't match `BitGrid::step`
+    pub fn step(&self, rule: &LifeRule) -> Self {
+        let Some((min, max)) = self.set_bounds() else {
+            return Self::new();
+        };
+        let (min, max) = ((min.0 - 1, min.1 - 1), (max.0 + 1, max.1 + 1));
+        let bits = BITS as isize;
+        let words: HashSet<(isize, isize)> = self
+            .tiles
+            .keys()
+            .flat_map(|&(tx, ty)| {
+                (ty * bits - 1..=ty * bits + bits)
+                    .flat_map(move |y| (tx - 1..=tx + 1).map(move |tx| (tx * bits, y)))
+            })
+            .collect();
+
+        let mut next = Self::new();
+        let mut values = Vec::new();
+        for (x, y) in words {
+            if y < min.1 || y > max.1 || x > max.0 || x + bits - 1 < min.0 {
+                continue;
+            }
+            let surround = surround_words(|x, y| self.word_at(x, y), x, y);
+            let mut word = rule.next_word(&surround, &mut values);
+            if min.0 > x {
+                word &= usize::MAX << (min.0 - x);
+            }
+            if max.0 < x + bits - 1 {
+                word &= usize::MAX >> (x + bits - 1 - max.0);
+            }
+            if word == 0 {
+                continue;
+            }
+            next.set.include(x + word.trailing_zeros() as isize, y);
+            next.set
+                .include(x + bits - 1 - word.leading_zeros() as isize, y);
+            next.or_word_at(x, y, word);
+        }
+        next
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bit_grid::random_cells;
+
+    // One generation a cell at a time with `get_surround`
+    fn step_cells(grid: &BitGrid, rule: &LifeRule) -> BitGrid {
+        let mut grid = grid.clone();
+        let mut next = BitGrid::new();
+        let Some((min, max)) = grid.set_bounds() else {
+            return next;
+        };
+        for y in min.1 - 1..=max.1 + 1 {
+            for x in min.0 - 1..=max.0 + 1 {
+                if rule.next(grid.get_surround(x, y)) {
+                    next.set_bit(x, y);
+                }
+            }
+        }
+        next
+    }
+
+    #[test]
+    fn rule() {
+        let conway: LifeRule = "B3/S23".parse().unwrap();
+        assert_eq!(conway, LifeRule::conway());
+        assert_eq!(
+            "b36/s23".parse::<LifeRule>().unwrap(),
+            LifeRule::new(&[3, 6], &[2, 3])
+        );
+        assert!("B3".parse::<LifeRule>().is_err());
+        assert!("B9/S23".parse::<LifeRule>().is_err());
+
+        let table = LifeRule::from_table(|s| conway.next(s));
+        for surround in 0..512 {
+            assert_eq!(
+                table.next(surround),
+                conway.next(surround),
+                "{surround:09b}"
+            );
+        }
+        assert!(conway.next(0b000_111_000));
+        assert!(!conway.next(0b000_010_000));
+        assert!(conway.next(0b100_000_011));
+    }
+
+    #[test]
+    fn bit_grid() {
+        // Odd bit order, so the table rule isn't symmetric
+        let rules = [
+            LifeRule::conway(),
+            "B36/S23".parse().unwrap(),
+            LifeRule::from_table(|s| s.count_ones() % 2 == 1 || s & 0b100_000_001 == 1),
+        ];
+        for rule in rules.iter() {
+            let mut grid = BitGrid::new();
+            for (x, y) in random_cells(0x2545_f491_4f6c_dd1d, (-70, -3), 140, 20) {
+                grid.set_bit(x, y);
+            }
+            for _ in 0..10 {
+                let next = grid.step(rule);
+                let expected = step_cells(&grid, rule);
+                assert_eq!(
+                    next.iter_set().collect::<Vec<_>>(),
+                    expected.iter_set().collect::<Vec<_>>()
+                );
+                grid = next;
+            }
+        }
+
+        // Sparse grids step the same, however far apart their patterns are
+        for rule in rules.iter() {
+            let mut grid: BitGrid = BitGrid::new();
+            let mut sparse = SparseBitGrid::new();
+            for (x, y) in random_cells(0x9e37_79b9_7f4a_7c15, (-70, -3), 140, 20) {
+                grid.set_bit(x, y);
+                sparse.set_bit(x, y);
+                sparse.set_bit(x + isize::MAX / 2, y);
+            }
+            for _ in 0..10 {
+                grid = grid.step(rule);
+                sparse = sparse.step(rule);
+                let far = grid.iter_set().map(|(x, y)| (x + isize::MAX / 2, y));
+                let mut expected: Vec<_> = grid.iter_set().chain(far).collect();
+                expected.sort_unstable_by_key(|&(x, y)| (y, x));
+                assert_eq!(sparse.iter_set().collect::<Vec<_>>(), expected);
+            }
+        }
+        assert!(SparseBitGrid::new()
+            .step(&LifeRule::conway())
+            .set_bounds()
+            .is_none());
+
+        let mut blinker: BitGrid = BitGrid::new();
+        (0..3).for_each(|x| blinker.set_bit(x, 0));
+        let next = blinker.step(&LifeRule::conway());
+        assert_eq!(next.set_bounds(), Some(((1, -1), (1, 1))));
+        assert_eq!(
+            next.step(&LifeRule::conway()).set_bounds(),
+            Some(((0, 0), (2, 0)))
+        );
+        assert!(BitGrid::<10, 10>::new()
+            .step(&LifeRule::conway())
+            .set_bounds()
+            .is_none());
+    }
+
+    #[test]
+    fn bit_grid_const() {
+        let rule = LifeRule::conway();
+        let mut grid: BitGridConst<-64, -2, 128, 8> = BitGridConst::new();
+        // Glider heading down and right, and a blinker cut off by the top edge
+        for (x, y) in [
+            (-2, 0),
+            (-1, 1),
+            (-3, 2),
+            (-2, 2),
+            (-1, 2),
+            (60, -2),
+            (61, -2),
+            (62, -2),
+        ] {
+            grid.set_bit(x, y);
+        }
+        for _ in 0..4 {
+            grid = grid.step(&rule);
+        }
+        let cells: Vec<_> = grid.iter_set().collect();
+        assert_eq!(cells, [(-1, 1), (0, 2), (-2, 3), (-1, 3), (0, 3)]);
+    }
+}
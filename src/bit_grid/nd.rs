@@ -0,0 +1,232 @@
+use super::BITS;
+use crate::SetBits;
+
+// Dense N dimensional grid that grows to fit, leaving `PAD` spare cells on every side when it does
+#[derive(Clone)]
+pub struct BitGridN<const N: usize, const PAD: usize = 10> {
+    grid: Vec<usize>,
+    min: [isize; N],
+    size: [usize; N],
+    min_set: [isize; N],
+    max_set: [isize; N],
+}
+
+pub type BitGrid3D<const PAD: usize = 10> = BitGridN<3, PAD>;
+pub type BitGrid4D<const PAD: usize = 10> = BitGridN<4, PAD>;
+
+impl<const N: usize, const PAD: usize> Default for BitGridN<N, PAD> {
+    fn default() -> Self {
+        Self {
+            grid: Vec::new(),
+            min: [0; N],
+            size: [0; N],
+            min_set: [isize::MAX; N],
+            max_set: [isize::MIN; N],
+        }
+    }
+}
+
+impl<const N: usize, const PAD: usize> BitGridN<N, PAD> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Dimension 0 varies fastest
+    fn index(&self, p: &[isize; N]) -> Option<usize> {
+        let mut index = 0;
+        let mut stride = 1;
+        for ((v, min), size) in p.iter().zip(self.min).zip(self.size) {
+            let n = v - min;
+            if n < 0 || n as usize >= size {
+                return None;
+            }
+            index += n as usize * stride;
+            stride *= size;
+        }
+        Some(index)
+    }
+
+    fn point(&self, mut index: usize) -> [isize; N] {
+        std::array::from_fn(|d| {
+            let v = self.min[d] + (index % self.size[d]) as isize;
+            index /= self.size[d];
+            v
+        })
+    }
+
+    fn resize(&mut self, min: [isize; N], max: [isize; N]) {
+        let size: [usize; N] = std::array::from_fn(|d| (max[d] - min[d] + 1) as usize);
+        let mut resized = Self {
+            grid: vec![0; size.iter().product::<usize>().div_ceil(BITS)],
+            min,
+            size,
+            min_set: self.min_set,
+            max_set: self.max_set,
+        };
+        for p in self.iter_set() {
+            let index = resized.index(&p).unwrap();
+            resized.grid[index / BITS] |= 1 << (index % BITS);
+        }
+        *self = resized;
+    }
+
+    pub fn set_bit(&mut self, p: [isize; N]) {
+        for (d, v) in p.into_iter().enumerate() {
+            self.min_set[d] = self.min_set[d].min(v);
+            self.max_set[d] = self.max_set[d].max(v);
+        }
+        let index = match self.index(&p) {
+            Some(index) => index,
+            None => {
+                self.resize(
+                    self.min_set.map(|v| v - PAD as isize),
+                    self.max_set.map(|v| v + PAD as isize),
+                );
+                self.index(&p).unwrap()
+            }
+        };
+        self.grid[index / BITS] |= 1 << (index % BITS);
+    }
+
+    pub fn clear_bit(&mut self, p: [isize; N]) {
+        if let Some(index) = self.index(&p) {
+            self.grid[index / BITS] &= !(1 << (index % BITS));
+        }
+    }
+
+    pub fn bit_is_set(&self, p: [isize; N]) -> bool {
+        match self.index(&p) {
+            Some(index) => self.grid[index / BITS] & (1 << (index % BITS)) != 0,
+            None => false,
+        }
+    }
+
+    // The 3^N cells around `p`.  Offset `d` is bit `sum((d[k] + 1) * 3^k)`, so `p` itself is bit
+    // `(3^N - 1) / 2`, 13 in 3D and 40 in 4D.
+    pub fn get_surround(&self, p: [isize; N]) -> u128 {
+        assert!(N <= 4, "{N} dimensional surround doesn't fit in a u128");
+        let mut surround = 0;
+        for bit in 0..3usize.pow(N as u32) {
+            let mut q = p;
+            let mut rest = bit;
+            for v in q.iter_mut() {
+                *v += (rest % 3) as isize - 1;
+                rest /= 3;
+            }
+            if self.bit_is_set(q) {
+                surround |= 1 << bit;
+            }
+        }
+        surround
+    }
+
+    // Set cells in the surround, not counting `p`
+    pub fn count_neighbors(&self, p: [isize; N]) -> usize {
+        let centre = (3u128.pow(N as u32) - 1) / 2;
+        (self.get_surround(p) & !(1 << centre)).count_ones() as usize
+    }
+
+    pub fn clear(&mut self) {
+        self.grid.iter_mut().for_each(|v| *v = 0);
+    }
+
+    pub fn count_set(&self) -> usize {
+        self.grid.iter().map(|v| v.count_ones() as usize).sum()
+    }
+
+    pub fn iter_set(&self) -> impl Iterator<Item = [isize; N]> + '_ {
+        SetBits::new(&self.grid).map(|index| self.point(index))
+    }
+
+    // Per dimension, the same as `BitGrid::set_bounds`
+    pub fn set_bounds(&self) -> Option<([isize; N], [isize; N])> {
+        if N == 0 || self.min_set[0] > self.max_set[0] {
+            None
+        } else {
+            Some((self.min_set, self.max_set))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Every point within one of the set bounds
+    fn points<const N: usize>(min: [isize; N], max: [isize; N]) -> Vec<[isize; N]> {
+        let mut points = vec![[0; N]];
+        for d in 0..N {
+            points = points
+                .into_iter()
+                .flat_map(|p| {
+                    (min[d] - 1..=max[d] + 1).map(move |v| {
+                        let mut p = p;
+                        p[d] = v;
+                        p
+                    })
+                })
+                .collect();
+        }
+        points
+    }
+
+    // Conway Cubes
+    fn cycle<const N: usize>(grid: &BitGridN<N>) -> BitGridN<N> {
+        let mut next = BitGridN::new();
+        let Some((min, max)) = grid.set_bounds() else {
+            return next;
+        };
+        for p in points(min, max) {
+            let neighbors = grid.count_neighbors(p);
+            if neighbors == 3 || (neighbors == 2 && grid.bit_is_set(p)) {
+                next.set_bit(p);
+            }
+        }
+        next
+    }
+
+    #[test]
+    fn conway_cubes() {
+        let mut grid: BitGrid3D = BitGrid3D::new();
+        let mut grid_4d: BitGrid4D = BitGrid4D::new();
+        for (y, line) in [".#.", "..#", "###"].iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    grid.set_bit([x as isize, y as isize, 0]);
+                    grid_4d.set_bit([x as isize, y as isize, 0, 0]);
+                }
+            }
+        }
+        assert_eq!(grid.count_set(), 5);
+        assert_eq!(grid.get_surround([1, 1, 0]), 0b111_100_010 << 9);
+        assert_eq!(grid.count_neighbors([1, 1, 0]), 5);
+        assert_eq!(grid.count_neighbors([1, 1, 1]), 5);
+
+        for _ in 0..6 {
+            grid = cycle(&grid);
+            grid_4d = cycle(&grid_4d);
+        }
+        assert_eq!(grid.count_set(), 112);
+        assert_eq!(grid_4d.count_set(), 848);
+        assert_eq!(grid.iter_set().count(), 112);
+    }
+
+    #[test]
+    fn resize() {
+        let mut grid: BitGridN<2, 1> = BitGridN::new();
+        assert_eq!(grid.set_bounds(), None);
+        assert!(!grid.bit_is_set([5, 5]));
+        let bits = [[0, 0], [-30, 4], [7, -100], [2, 2]];
+        for p in bits {
+            grid.set_bit(p);
+        }
+        grid.clear_bit([2, 2]);
+        grid.clear_bit([1000, 0]);
+        let mut set: Vec<_> = grid.iter_set().collect();
+        set.sort();
+        assert_eq!(set, [[-30, 4], [0, 0], [7, -100]]);
+        assert_eq!(grid.set_bounds(), Some(([-30, -100], [7, 4])));
+        grid.clear();
+        assert_eq!(grid.count_set(), 0);
+    }
+}
@@ -0,0 +1,355 @@
+use super::{word_starts, BitGrid, BitGridConst, Bounds, SparseBitGrid, BITS};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+fn union(a: Option<Bounds>, b: Option<Bounds>) -> Option<Bounds> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some((
+            (a.0 .0.min(b.0 .0), a.0 .1.min(b.0 .1)),
+            (a.1 .0.max(b.1 .0), a.1 .1.max(b.1 .1)),
+        )),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn intersection(a: Option<Bounds>, b: Option<Bounds>) -> Option<Bounds> {
+    let (a, b) = (a?, b?);
+    let min = (a.0 .0.max(b.0 .0), a.0 .1.max(b.0 .1));
+    let max = (a.1 .0.min(b.1 .0), a.1 .1.min(b.1 .1));
+    if min.0 <= max.0 && min.1 <= max.1 {
+        Some((min, max))
+    } else {
+        None
+    }
+}
+
+// Nothing is set outside of the set bounds, so whole words are combined without masking.  The
+// result's set bounds are the union of both set bounds, or the intersection for `&`.
+impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
+    fn combine<F>(
+        &self,
+        rhs: &Self,
+        bounds: fn(Option<Bounds>, Option<Bounds>) -> Option<Bounds>,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        let Some((min, max)) = bounds(self.set_bounds(), rhs.set_bounds()) else {
+            return Self::new();
+        };
+        let mut combined = Self::with_set_bounds(min, max);
+        for y in min.1..=max.1 {
+            for x in word_starts(min.0, max.0) {
+                combined.or_word_at(x, y, f(self.word_at(x, y), rhs.word_at(x, y)));
+            }
+        }
+        combined
+    }
+
+    // Every bit moved by (dx, dy)
+    pub fn shifted(&self, dx: isize, dy: isize) -> Self {
+        let Some((min, max)) = self.set_bounds() else {
+            return Self::new();
+        };
+        let mut shifted = Self::with_set_bounds((min.0 + dx, min.1 + dy), (max.0 + dx, max.1 + dy));
+        for y in min.1..=max.1 {
+            for x in (min.0..=max.0).step_by(BITS) {
+                shifted.or_word_at(x + dx, y + dy, self.word_at(x, y));
+            }
+        }
+        shifted
+    }
+}
+
+// Only the words stored in either grid are combined, every op leaves two clear words clear
+impl SparseBitGrid {
+    fn combine<F>(
+        &self,
+        rhs: &Self,
+        bounds: fn(Option<Bounds>, Option<Bounds>) -> Option<Bounds>,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        let Some((min, max)) = bounds(self.set_bounds(), rhs.set_bounds()) else {
+            return Self::new();
+        };
+        let mut combined = Self::with_set_bounds(min, max);
+        for (x, y, _) in self.words().chain(rhs.words()) {
+            combined.or_word_at(x, y, f(self.word_at(x, y), rhs.word_at(x, y)));
+        }
+        combined
+    }
+
+    // Every bit moved by (dx, dy)
+    pub fn shifted(&self, dx: isize, dy: isize) -> Self {
+        let Some((min, max)) = self.set_bounds() else {
+            return Self::new();
+        };
+        let mut shifted = Self::with_set_bounds((min.0 + dx, min.1 + dy), (max.0 + dx, max.1 + dy));
+        for (x, y, word) in self.words() {
+            shifted.or_word_at(x + dx, y + dy, word);
+        }
+        shifted
+    }
+}
+
+macro_rules! impl_op {
+    ($op_trait:ident, $op:ident, $assign_trait:ident, $assign:ident, $bounds:ident) => {
+        impl<const BX: usize, const BY: usize> $op_trait<&BitGrid<BX, BY>> for &BitGrid<BX, BY> {
+            type Output = BitGrid<BX, BY>;
+
+            fn $op(self, rhs: &BitGrid<BX, BY>) -> Self::Output {
+                self.combine(rhs, $bounds, |a, b| a.$op(b))
+            }
+        }
+
+        impl<const BX: usize, const BY: usize> $assign_trait<&BitGrid<BX, BY>> for BitGrid<BX, BY> {
+            fn $assign(&mut self, rhs: &BitGrid<BX, BY>) {
+                *self = (&*self).$op(rhs);
+            }
+        }
+
+        impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize>
+            $op_trait<&BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>>
+            for &BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+        {
+            type Output = BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>;
+
+            fn $op(self, rhs: &BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>) -> Self::Output {
+                let mut result = self.clone();
+                result.$assign(rhs);
+                result
+            }
+        }
+
+        impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize>
+            $assign_trait<&BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>>
+            for BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+        {
+            fn $assign(&mut self, rhs: &BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>) {
+                self.grid
+                    .iter_mut()
+                    .zip(rhs.grid.iter())
+                    .for_each(|(a, b)| a.$assign(b));
+            }
+        }
+
+        impl $op_trait<&SparseBitGrid> for &SparseBitGrid {
+            type Output = SparseBitGrid;
+
+            fn $op(self, rhs: &SparseBitGrid) -> Self::Output {
+                self.combine(rhs, $bounds, |a, b| a.$op(b))
+            }
+        }
+
+        impl $assign_trait<&SparseBitGrid> for SparseBitGrid {
+            fn $assign(&mut self, rhs: &SparseBitGrid) {
+                *self = (&*self).$op(rhs);
+            }
+        }
+    };
+}
+
+impl_op!(BitAnd, bitand, BitAndAssign, bitand_assign, intersection);
+impl_op!(BitOr, bitor, BitOrAssign, bitor_assign, union);
+impl_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, union);
+
+// Every bit within the set bounds is flipped
+impl<const BX: usize, const BY: usize> Not for &BitGrid<BX, BY> {
+    type Output = BitGrid<BX, BY>;
+
+    fn not(self) -> Self::Output {
+        let Some((min, max)) = self.set_bounds() else {
+            return BitGrid::new();
+        };
+        let mut inverted = BitGrid::with_set_bounds(min, max);
+        for y in min.1..=max.1 {
+            for x in (min.0..=max.0).step_by(BITS) {
+                let remaining = max.0 - x + 1;
+                let mask = if remaining < BITS as isize {
+                    (1 << remaining) - 1
+                } else {
+                    usize::MAX
+                };
+                inverted.or_word_at(x, y, !self.word_at(x, y) & mask);
+            }
+        }
+        inverted
+    }
+}
+
+// Every bit within the set bounds is flipped, which fills in the whole box between far apart bits
+impl Not for &SparseBitGrid {
+    type Output = SparseBitGrid;
+
+    fn not(self) -> Self::Output {
+        let Some((min, max)) = self.set_bounds() else {
+            return SparseBitGrid::new();
+        };
+        let mut inverted = SparseBitGrid::with_set_bounds(min, max);
+        for y in min.1..=max.1 {
+            for x in word_starts(min.0, max.0) {
+                let mut mask = usize::MAX;
+                if min.0 > x {
+                    mask &= usize::MAX << (min.0 - x);
+                }
+                if max.0 < x + BITS as isize - 1 {
+                    mask &= usize::MAX >> (x + BITS as isize - 1 - max.0);
+                }
+                inverted.or_word_at(x, y, !self.word_at(x, y) & mask);
+            }
+        }
+        inverted
+    }
+}
+
+impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize> Not
+    for &BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+{
+    type Output = BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>;
+
+    fn not(self) -> Self::Output {
+        BitGridConst {
+            grid: self.grid.iter().map(|w| !w).collect(),
+        }
+    }
+}
+
+impl<const MIN_X: isize, const MIN_Y: isize, const WIDTH: usize, const HEIGHT: usize>
+    BitGridConst<MIN_X, MIN_Y, WIDTH, HEIGHT>
+{
+    // Every bit moved by (dx, dy), bits moved off of the grid are dropped
+    pub fn shifted(&self, dx: isize, dy: isize) -> Self {
+        let mut shifted = Self::new();
+        for y in MIN_Y..MIN_Y + HEIGHT as isize {
+            for x in (MIN_X..MIN_X + WIDTH as isize).step_by(BITS) {
+                shifted.or_word_at(x + dx, y + dy, self.word_at(x, y));
+            }
+        }
+        shifted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bit_grid::random_cells;
+    use std::collections::BTreeSet;
+
+    fn grid(bits: &BTreeSet<(isize, isize)>) -> BitGrid {
+        let mut grid = BitGrid::new();
+        bits.iter().for_each(|&(x, y)| grid.set_bit(x, y));
+        grid
+    }
+
+    fn set(grid: &BitGrid) -> BTreeSet<(isize, isize)> {
+        grid.iter_set().collect()
+    }
+
+    #[test]
+    fn bit_grid() {
+        let a: BTreeSet<_> = random_cells(0x2545_f491_4f6c_dd1d, (-100, -5), 150, 30).collect();
+        let b: BTreeSet<_> = random_cells(0x9e37_79b9_7f4a_7c15, (20, 10), 90, 40).collect();
+        let (ga, gb) = (grid(&a), grid(&b));
+
+        assert_eq!(set(&(&ga & &gb)), &a & &b);
+        assert_eq!(set(&(&ga | &gb)), &a | &b);
+        assert_eq!(set(&(&ga ^ &gb)), &a ^ &b);
+        let mut gc = ga.clone();
+        gc ^= &gb;
+        gc |= &gb;
+        gc &= &ga;
+        assert_eq!(set(&gc), a);
+
+        // Disjoint
+        let c = random_cells(1, (500, 500), 10, 10).collect();
+        assert_eq!((&ga & &grid(&c)).count_set(), 0);
+        assert_eq!((&ga & &BitGrid::new()).count_set(), 0);
+        assert_eq!(set(&(&ga | &BitGrid::new())), a);
+
+        let inverted = !&ga;
+        let (min, max) = ga.set_bounds().unwrap();
+        assert_eq!(inverted.set_bounds(), Some((min, max)));
+        for y in min.1..=max.1 {
+            for x in min.0..=max.0 {
+                assert_ne!(inverted.bit_is_set(x, y), ga.bit_is_set(x, y));
+            }
+        }
+        assert_eq!(set(&!&inverted), a);
+
+        for (dx, dy) in [(0, 0), (1, 0), (-1, 3), (64, -2), (-127, 70)] {
+            let shifted: BTreeSet<_> = a.iter().map(|(x, y)| (x + dx, y + dy)).collect();
+            assert_eq!(set(&ga.shifted(dx, dy)), shifted);
+        }
+    }
+
+    #[test]
+    fn sparse_bit_grid() {
+        let a: BTreeSet<_> = random_cells(0x2545_f491_4f6c_dd1d, (-100, -5), 150, 30).collect();
+        let b: BTreeSet<_> = random_cells(0x9e37_79b9_7f4a_7c15, (20, 10), 90, 40).collect();
+        let sparse = |bits: &BTreeSet<(isize, isize)>| {
+            let mut grid = SparseBitGrid::new();
+            bits.iter().for_each(|&(x, y)| grid.set_bit(x, y));
+            grid
+        };
+        let cells = |grid: &SparseBitGrid| grid.iter_set().collect::<BTreeSet<_>>();
+        let (sa, sb) = (sparse(&a), sparse(&b));
+        let (ga, gb) = (grid(&a), grid(&b));
+
+        assert_eq!(cells(&(&sa & &sb)), &a & &b);
+        assert_eq!(cells(&(&sa | &sb)), &a | &b);
+        assert_eq!(cells(&(&sa ^ &sb)), &a ^ &b);
+        assert_eq!((&sa & &sb).set_bounds(), (&ga & &gb).set_bounds());
+        assert_eq!((&sa ^ &sb).set_bounds(), (&ga ^ &gb).set_bounds());
+        let mut sc = sa.clone();
+        sc ^= &sb;
+        sc |= &sb;
+        sc &= &sa;
+        assert_eq!(cells(&sc), a);
+
+        let inverted = !&sa;
+        assert_eq!(inverted.set_bounds(), sa.set_bounds());
+        assert_eq!(cells(&inverted), set(&!&ga));
+        assert_eq!(cells(&!&inverted), a);
+
+        for (dx, dy) in [(0, 0), (1, 0), (-1, 3), (64, -2), (-127, 70)] {
+            assert_eq!(cells(&sa.shifted(dx, dy)), set(&ga.shifted(dx, dy)));
+            assert_eq!(
+                sa.shifted(dx, dy).set_bounds(),
+                ga.shifted(dx, dy).set_bounds()
+            );
+        }
+
+        // Far apart grids only touch their own tiles
+        let far = sparse(&a).shifted(isize::MAX / 2, 0);
+        assert_eq!((&sa | &far).tiles(), sa.tiles() + far.tiles());
+        assert_eq!((&sa & &far).tiles(), 0);
+    }
+
+    #[test]
+    fn bit_grid_const() {
+        type Grid = BitGridConst<-64, -2, 128, 6>;
+        let mut a = Grid::new();
+        let mut b = Grid::new();
+        a.set_bit(-64, -2);
+        a.set_bit(5, 0);
+        b.set_bit(5, 0);
+        b.set_bit(63, 3);
+
+        let and: Vec<_> = (&a & &b).iter_set().collect();
+        assert_eq!(and, [(5, 0)]);
+        assert_eq!((&a | &b).iter_set().count(), 3);
+        assert_eq!((&a ^ &b).iter_set().count(), 2);
+        assert_eq!((!&a).iter_set().count(), 128 * 6 - 2);
+        a |= &b;
+        assert_eq!(a.iter_set().count(), 3);
+
+        let shifted: Vec<_> = a.shifted(1, 1).iter_set().collect();
+        assert_eq!(shifted, [(-63, -1), (6, 1)]);
+        let shifted: Vec<_> = a.shifted(-70, 0).iter_set().collect();
+        assert_eq!(shifted, [(-7, 3)]);
+    }
+}
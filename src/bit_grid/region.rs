@@ -0,0 +1,93 @@
+use super::{BitGrid, BITS};
+use crate::{Connectivity, Point2D, Region};
+
+impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
+    // Set bits connected to (x, y), empty if (x, y) isn't set
+    pub fn flood_fill(&self, x: isize, y: isize, connectivity: Connectivity) -> Region {
+        self.clone().take_region(x, y, connectivity)
+    }
+
+    pub fn regions(&self, connectivity: Connectivity) -> Vec<Region> {
+        let Some((min, max)) = self.set_bounds() else {
+            return Vec::new();
+        };
+        let mut remaining = self.clone();
+        let mut regions = Vec::new();
+        for y in min.1..=max.1 {
+            let mut x = min.0;
+            while x <= max.0 {
+                let word = remaining.word_at(x, y);
+                if word == 0 {
+                    x += BITS as isize;
+                } else {
+                    let start = x + word.trailing_zeros() as isize;
+                    regions.push(remaining.take_region(start, y, connectivity));
+                }
+            }
+        }
+        regions
+    }
+
+    fn take_region(&mut self, x: isize, y: isize, connectivity: Connectivity) -> Region {
+        Region::flood_fill(
+            Point2D::new(x, y),
+            |p| connectivity.neighbors(p),
+            |p| {
+                if self.bit_is_set(p.x, p.y) {
+                    self.clear_bit(p.x, p.y);
+                    true
+                } else {
+                    false
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn regions() {
+        let mut grid: BitGrid = BitGrid::new();
+        for (y, line) in ["##..#", "#..#.", "....#", "###.#"].iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    grid.set_bit(x as isize + 60, y as isize - 3);
+                }
+            }
+        }
+
+        let mut areas: Vec<usize> = grid
+            .regions(Connectivity::Four)
+            .iter()
+            .map(|r| r.area())
+            .collect();
+        areas.sort();
+        assert_eq!(areas, [1, 1, 2, 3, 3]);
+
+        let regions = grid.regions(Connectivity::Eight);
+        assert_eq!(regions.len(), 3);
+        let corner = grid.flood_fill(60, -3, Connectivity::Four);
+        assert_eq!(
+            (corner.area(), corner.perimeter(), corner.sides()),
+            (3, 8, 6)
+        );
+        assert!(regions.contains(&corner));
+        assert_eq!(grid.flood_fill(61, -2, Connectivity::Four).area(), 0);
+        assert_eq!(grid.count_set(), 10);
+
+        let mut grid: BitGrid = BitGrid::new();
+        for y in 0..250 {
+            for x in 0..1000 {
+                if x != 500 || y == 249 {
+                    grid.set_bit(x, y);
+                }
+            }
+        }
+        let regions = grid.regions(Connectivity::Four);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].sides(), 8);
+    }
+}
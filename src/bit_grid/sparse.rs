@@ -0,0 +1,228 @@
+use super::{BitGridDisplay, Bounds, SetBounds, BITS};
+use crate::{Frame, HashMap, Point2D, Render, SetBits};
+use std::fmt;
+
+type Tile = [usize; BITS];
+
+// Only the `BITS` x `BITS` tiles holding set bits are stored, for grids too spread out for
+// `BitGrid`
+#[derive(Clone, Default)]
+pub struct SparseBitGrid {
+    pub(super) tiles: HashMap<(isize, isize), Tile>,
+    pub(super) set: SetBounds,
+}
+
+// Tile, row within the tile and bit within the row
+fn tile_bit(x: isize, y: isize) -> ((isize, isize), usize, usize) {
+    let bits = BITS as isize;
+    (
+        (x.div_euclid(bits), y.div_euclid(bits)),
+        y.rem_euclid(bits) as usize,
+        x.rem_euclid(bits) as usize,
+    )
+}
+
+impl SparseBitGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn with_set_bounds(min: (isize, isize), max: (isize, isize)) -> Self {
+        let mut grid = Self::new();
+        grid.set.include(min.0, min.1);
+        grid.set.include(max.0, max.1);
+        grid
+    }
+
+    pub fn set_bit(&mut self, x: isize, y: isize) {
+        self.set.include(x, y);
+        let (tile, row, bit) = tile_bit(x, y);
+        self.tiles.entry(tile).or_insert([0; BITS])[row] |= 1 << bit;
+    }
+
+    // Tiles are dropped once they're empty
+    pub fn clear_bit(&mut self, x: isize, y: isize) {
+        let (key, row, bit) = tile_bit(x, y);
+        if let Some(tile) = self.tiles.get_mut(&key) {
+            tile[row] &= !(1 << bit);
+            if tile.iter().all(|&word| word == 0) {
+                self.tiles.remove(&key);
+            }
+        }
+    }
+
+    pub fn bit_is_set(&self, x: isize, y: isize) -> bool {
+        let (tile, row, bit) = tile_bit(x, y);
+        self.tiles
+            .get(&tile)
+            .is_some_and(|tile| tile[row] & (1 << bit) != 0)
+    }
+
+    // `BITS` bits of row `y` starting at `x`, with bit 0 being `x`
+    pub(super) fn word_at(&self, x: isize, y: isize) -> usize {
+        let (tile, row, shift) = tile_bit(x, y);
+        let word = |tx| self.tiles.get(&(tx, tile.1)).map_or(0, |tile| tile[row]);
+        if shift == 0 {
+            word(tile.0)
+        } else {
+            (word(tile.0) >> shift) | (word(tile.0 + 1) << (BITS - shift))
+        }
+    }
+
+    // Doesn't touch the set bounds, and never allocates an empty tile
+    pub(super) fn or_word_at(&mut self, x: isize, y: isize, v: usize) {
+        let (tile, row, shift) = tile_bit(x, y);
+        let mut or = |tx, word| {
+            if word != 0 {
+                self.tiles.entry((tx, tile.1)).or_insert([0; BITS])[row] |= word;
+            }
+        };
+        or(tile.0, v << shift);
+        if shift != 0 {
+            or(tile.0 + 1, v >> (BITS - shift));
+        }
+    }
+
+    // (x, y, word) of every non-empty word, with x a multiple of `BITS`, in no particular order
+    pub(super) fn words(&self) -> impl Iterator<Item = (isize, isize, usize)> + '_ {
+        self.tiles.iter().flat_map(|(&(tx, ty), tile)| {
+            tile.iter()
+                .enumerate()
+                .filter(|(_, &word)| word != 0)
+                .map(move |(row, &word)| {
+                    (tx * BITS as isize, ty * BITS as isize + row as isize, word)
+                })
+        })
+    }
+
+    // Same layout as `BitGrid::get_surround`
+    pub fn get_surround(&self, x: isize, y: isize) -> u16 {
+        let row = |y| (self.word_at(x - 1, y) & 0b111) as u16;
+        (row(y - 1) << 6) | (row(y) << 3) | row(y + 1)
+    }
+
+    pub fn clear(&mut self) {
+        self.tiles.clear();
+        self.set = SetBounds::default();
+    }
+
+    pub fn count_set(&self) -> usize {
+        self.tiles
+            .values()
+            .flatten()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // Row by row, the same order as `BitGrid::iter_set`
+    pub fn iter_set(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        let mut set: Vec<(isize, isize)> = self
+            .tiles
+            .iter()
+            .flat_map(|(&(tx, ty), tile)| {
+                SetBits::new(tile).map(move |i| {
+                    (
+                        tx * BITS as isize + (i % BITS) as isize,
+                        ty * BITS as isize + (i / BITS) as isize,
+                    )
+                })
+            })
+            .collect();
+        set.sort_unstable_by_key(|&(x, y)| (y, x));
+        set.into_iter()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point2D<isize>> + '_ {
+        self.iter_set().map(|(x, y)| Point2D::new(x, y))
+    }
+
+    // Number of tiles allocated
+    pub fn tiles(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn set_bounds(&self) -> Option<Bounds> {
+        self.set.get()
+    }
+
+    pub fn display(&self) -> BitGridDisplay<'_> {
+        BitGridDisplay::new(self.tight_bounds(), |x, y| self.bit_is_set(x, y))
+    }
+}
+
+impl fmt::Display for SparseBitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+impl Render for SparseBitGrid {
+    fn render(&self) -> Frame {
+        self.display().render()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::BitGrid;
+
+    #[test]
+    fn sparse() {
+        let mut sparse = SparseBitGrid::new();
+        let mut dense: BitGrid = BitGrid::new();
+        assert_eq!(sparse.set_bounds(), None);
+        let bits = [
+            (-1, -1),
+            (0, -1),
+            (63, 0),
+            (64, 0),
+            (-64, 63),
+            (-65, 64),
+            (5, 200),
+        ];
+        for (x, y) in bits {
+            sparse.set_bit(x, y);
+            dense.set_bit(x, y);
+        }
+        assert_eq!(sparse.tiles(), 7);
+        assert_eq!(sparse.count_set(), bits.len());
+        assert_eq!(
+            sparse.iter_set().collect::<Vec<_>>(),
+            dense.iter_set().collect::<Vec<_>>()
+        );
+        assert_eq!(sparse.set_bounds(), dense.set_bounds());
+        assert_eq!(sparse.to_string(), dense.to_string());
+        assert_eq!(sparse.render().to_string(), dense.render().to_string());
+
+        // Surrounds across tile edges
+        let (min, max) = dense.set_bounds().unwrap();
+        for y in min.1 - 1..=max.1 + 1 {
+            for x in min.0 - 1..=max.0 + 1 {
+                assert_eq!(
+                    sparse.get_surround(x, y),
+                    dense.get_surround(x, y),
+                    "{x},{y}"
+                );
+            }
+        }
+
+        sparse.clear_bit(5, 200);
+        sparse.clear_bit(1_000_000, -1_000_000);
+        assert_eq!(sparse.tiles(), 6);
+        assert!(!sparse.bit_is_set(5, 200));
+        assert!(sparse.bit_is_set(-65, 64));
+
+        // Far apart bits only cost a tile each
+        sparse.set_bit(isize::MAX / 2, isize::MIN / 2);
+        assert_eq!(sparse.tiles(), 7);
+        assert_eq!(
+            sparse.points().next(),
+            Some(Point2D::new(isize::MAX / 2, isize::MIN / 2))
+        );
+        sparse.clear();
+        assert_eq!(sparse.count_set(), 0);
+        assert_eq!(sparse.set_bounds(), None);
+        assert_eq!(sparse.to_string(), "");
+    }
+}
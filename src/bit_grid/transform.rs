@@ -0,0 +1,314 @@
+use super::{BitGrid, BITS};
+
+fn size(min: (isize, isize), max: (isize, isize)) -> (usize, usize) {
+    ((max.0 - min.0 + 1) as usize, (max.1 - min.1 + 1) as usize)
+}
+
+// Bit x of word y swaps with bit y of word x, by swapping ever smaller off diagonal blocks
+fn transpose_block(block: &mut [usize; BITS]) {
+    let mut j = BITS / 2;
+    let mut mask = usize::MAX >> j;
+    while j != 0 {
+        let mut k = 0;
+        while k < BITS {
+            let t = ((block[k] >> j) ^ block[k + j]) & mask;
+            block[k] ^= t << j;
+            block[k + j] ^= t;
+            k = (k + j + 1) & !j;
+        }
+        j >>= 1;
+        mask ^= mask << j;
+    }
+}
+
+// Transformations work on the set bounds, and keep the top left corner of the set bounds where it
+// is.  Nothing is set outside of the set bounds, so whole words are moved without masking.
+impl<const BX: usize, const BY: usize> BitGrid<BX, BY> {
+    pub fn transpose(&self) -> Self {
+        let Some((min, max)) = self.set_bounds() else {
+            return Self::new();
+        };
+        let (width, height) = size(min, max);
+        let mut transposed = Self::with_set_bounds(
+            min,
+            (min.0 + height as isize - 1, min.1 + width as isize - 1),
+        );
+
+        let mut block = [0; BITS];
+        for by in (0..height).step_by(BITS) {
+            for bx in (0..width).step_by(BITS) {
+                for (r, word) in block.iter_mut().enumerate() {
+                    *word = self.word_at(min.0 + bx as isize, min.1 + (by + r) as isize);
+                }
+                transpose_block(&mut block);
+                for (c, word) in block.iter().enumerate().take(width - bx) {
+                    transposed.or_word_at(min.0 + by as isize, min.1 + (bx + c) as isize, *word);
+                }
+            }
+        }
+
+        transposed
+    }
+
+    // Mirror left to right
+    pub fn flip_horizontal(&self) -> Self {
+        let Some((min, max)) = self.set_bounds() else {
+            return Self::new();
+        };
+        let (width, _) = size(min, max);
+        let mut flipped = Self::with_set_bounds(min, max);
+
+        for y in min.1..=max.1 {
+            for i in 0..width.div_ceil(BITS) {
+                let word = self.word_at(min.0 + (i * BITS) as isize, y);
+                let x = min.0 + width as isize - ((i + 1) * BITS) as isize;
+                flipped.or_word_at(x, y, word.reverse_bits());
+            }
+        }
+
+        flipped
+    }
+
+    // Mirror top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        let Some((min, max)) = self.set_bounds() else {
+            return Self::new();
+        };
+        let (width, _) = size(min, max);
+        let mut flipped = Self::with_set_bounds(min, max);
+
+        for y in min.1..=max.1 {
+            for i in 0..width.div_ceil(BITS) {
+                let x = min.0 + (i * BITS) as isize;
+                flipped.or_word_at(x, max.1 - (y - min.1), self.word_at(x, y));
+            }
+        }
+
+        flipped
+    }
+
+    // Clockwise, with y increasing downwards
+    pub fn rotate_90(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    pub fn rotate_270(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    // The 4 rotations, followed by the 4 rotations of the horizontal flip
+    pub fn symmetries(&self) -> [Self; 8] {
+        let flipped = self.flip_horizontal();
+        [
+            self.clone(),
+            self.rotate_90(),
+            self.rotate_180(),
+            self.rotate_270(),
+            flipped.clone(),
+            flipped.rotate_90(),
+            flipped.rotate_180(),
+            flipped.rotate_270(),
+        ]
+    }
+
+    pub fn view(&self, min: (isize, isize), max: (isize, isize)) -> BitGridView<'_, BX, BY> {
+        assert!(min.0 <= max.0 + 1 && min.1 <= max.1 + 1);
+        BitGridView {
+            grid: self,
+            min,
+            width: size(min, max).0,
+            height: size(min, max).1,
+        }
+    }
+}
+
+// A borrowed rectangle of a `BitGrid`, addressed from (0, 0) at its top left
+#[derive(Copy, Clone)]
+pub struct BitGridView<'a, const BX: usize = 10, const BY: usize = 10> {
+    grid: &'a BitGrid<BX, BY>,
+    min: (isize, isize),
+    width: usize,
+    height: usize,
+}
+
+impl<const BX: usize, const BY: usize> BitGridView<'_, BX, BY> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bit_is_set(&self, x: isize, y: isize) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as usize) < self.width
+            && (y as usize) < self.height
+            && self.grid.bit_is_set(self.min.0 + x, self.min.1 + y)
+    }
+
+    // Up to `usize::BITS` bits of row `y` starting at `x`, with bit 0 being `x`
+    pub fn word(&self, x: usize, y: usize) -> usize {
+        if x >= self.width || y >= self.height {
+            return 0;
+        }
+        let word = self
+            .grid
+            .word_at(self.min.0 + x as isize, self.min.1 + y as isize);
+        let remaining = self.width - x;
+        if remaining < BITS {
+            word & ((1 << remaining) - 1)
+        } else {
+            word
+        }
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.width).step_by(BITS).map(move |x| self.word(x, y))
+    }
+
+    pub fn count_set(&self) -> usize {
+        (0..self.height)
+            .flat_map(|y| self.row(y))
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn to_grid(&self) -> BitGrid<BX, BY> {
+        if self.width == 0 || self.height == 0 {
+            return BitGrid::new();
+        }
+        let mut grid =
+            BitGrid::with_set_bounds((0, 0), (self.width as isize - 1, self.height as isize - 1));
+        for y in 0..self.height {
+            for (i, word) in self.row(y).enumerate() {
+                grid.or_word_at((i * BITS) as isize, y as isize, word);
+            }
+        }
+        grid
+    }
+}
+
+impl<const BX: usize, const BY: usize> PartialEq for BitGridView<'_, BX, BY> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && (0..self.height).all(|y| self.row(y).eq(other.row(y)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bit_grid::random_cells;
+
+    fn pattern(min: (isize, isize), width: isize, height: isize) -> BitGrid {
+        let mut grid = BitGrid::new();
+        for (x, y) in random_cells(0x2545_f491_4f6c_dd1d, min, width, height) {
+            grid.set_bit(x, y);
+        }
+        // Make sure the corners define the set bounds
+        grid.set_bit(min.0, min.1);
+        grid.set_bit(min.0 + width - 1, min.1 + height - 1);
+        grid
+    }
+
+    fn check<F>(grid: &BitGrid, transformed: &BitGrid, size: (isize, isize), f: F)
+    where
+        F: Fn(isize, isize) -> (isize, isize),
+    {
+        let (min, max) = grid.set_bounds().unwrap();
+        assert_eq!(transformed.set_bounds().unwrap().0, min);
+        assert_eq!(transformed.count_set(), grid.count_set());
+        for y in min.1..=max.1 {
+            for x in min.0..=max.0 {
+                let (tx, ty) = f(x - min.0, y - min.1);
+                assert_eq!(
+                    grid.bit_is_set(x, y),
+                    transformed.bit_is_set(min.0 + tx, min.1 + ty),
+                    "{x},{y} -> {tx},{ty}"
+                );
+            }
+        }
+        let (tmin, tmax) = transformed.set_bounds().unwrap();
+        assert_eq!((tmax.0 - tmin.0 + 1, tmax.1 - tmin.1 + 1), size);
+    }
+
+    #[test]
+    fn transpose_block() {
+        let mut block = [0; BITS];
+        for (y, word) in block.iter_mut().enumerate() {
+            *word = (y * 0x9e37_79b9) | (1 << (BITS - 1 - y));
+        }
+        let original = block;
+        super::transpose_block(&mut block);
+        for (y, row) in original.iter().enumerate() {
+            for (x, column) in block.iter().enumerate() {
+                assert_eq!(row >> x & 1, column >> y & 1);
+            }
+        }
+    }
+
+    #[test]
+    fn transforms() {
+        for (min, w, h) in [((-70, 5), 150, 70), ((3, -2), 5, 3), ((0, 0), 64, 1)] {
+            let grid = pattern(min, w, h);
+            check(&grid, &grid.transpose(), (h, w), |x, y| (y, x));
+            check(&grid, &grid.flip_horizontal(), (w, h), |x, y| {
+                (w - 1 - x, y)
+            });
+            check(&grid, &grid.flip_vertical(), (w, h), |x, y| (x, h - 1 - y));
+            check(&grid, &grid.rotate_90(), (h, w), |x, y| (h - 1 - y, x));
+            check(&grid, &grid.rotate_180(), (w, h), |x, y| {
+                (w - 1 - x, h - 1 - y)
+            });
+            check(&grid, &grid.rotate_270(), (h, w), |x, y| (y, w - 1 - x));
+        }
+
+        let grid = pattern((0, 0), 5, 3);
+        let symmetries = grid.symmetries();
+        check(&grid, &symmetries[0], (5, 3), |x, y| (x, y));
+        check(&grid, &symmetries[4], (5, 3), |x, y| (4 - x, y));
+        check(&grid, &symmetries[7], (3, 5), |x, y| (y, x));
+        for i in 1..4 {
+            assert!(symmetries[i] == symmetries[i - 1].rotate_90());
+            assert!(symmetries[i + 4] == symmetries[i + 3].rotate_90());
+        }
+        for (i, a) in symmetries.iter().enumerate() {
+            for b in symmetries[i + 1..].iter() {
+                let bounds = a.set_bounds().unwrap();
+                assert!(
+                    bounds != b.set_bounds().unwrap()
+                        || a.view(bounds.0, bounds.1) != b.view(bounds.0, bounds.1)
+                );
+            }
+        }
+
+        assert!(BitGrid::<10, 10>::new().rotate_90().set_bounds().is_none());
+    }
+
+    #[test]
+    fn view() {
+        let grid = pattern((-70, 5), 150, 70);
+        let view = grid.view((-60, 10), (19, 12));
+        assert_eq!((view.width(), view.height()), (80, 3));
+        for y in 0..3 {
+            for x in 0..80 {
+                assert_eq!(view.bit_is_set(x, y), grid.bit_is_set(x - 60, y + 10));
+            }
+        }
+        assert!(!view.bit_is_set(80, 0));
+        assert_eq!(view.row(0).count(), 2);
+        assert_eq!(view.word(70, 1), view.row(1).nth(1).unwrap() >> 6);
+
+        let copy = view.to_grid();
+        assert_eq!(copy.count_set(), view.count_set());
+        assert!(copy.view((0, 0), (79, 2)) == view);
+        assert!(grid.view((-60, 11), (19, 13)) != view);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::parser::trim_start_bytes;
+use crate::{Error, ErrorContext, Integer, LinesOpt};
+use std::marker::PhantomData;
+
+// Lines borrowed from the input buffer, with the same options as `Lines`
+pub struct ByteLines<'a> {
+    rest: &'a [u8],
+    options: LinesOpt,
+    number: usize,
+    section: usize,
+    returned: bool,
+    // Blank lines removed by `LinesOpt::SECTIONS` since the last line, after the first line
+    blank: bool,
+}
+
+// Blank lines split sections, the same test as `Lines`
+fn is_blank(line: &[u8]) -> bool {
+    trim_start_bytes(line).is_empty()
+}
+
+impl<'a> ByteLines<'a> {
+    // Tabs can't be expanded without copying, so options with `LinesOpt::expand_tabs` are an error
+    pub fn new(buf: &'a [u8], options: LinesOpt) -> Result<Self, Error> {
+        if options.tab_width() != 0 {
+            return Err(Error::InvalidInput(
+                "ByteLines can't expand tabs, use Lines instead".into(),
+            ));
+        }
+        Ok(Self {
+            rest: buf,
+            options,
+            number: 0,
+            section: 0,
+            returned: false,
+            blank: false,
+        })
+    }
+
+    // Line number of the most recently returned line
+    pub fn line_number(&self) -> usize {
+        self.number
+    }
+
+    // Section of the most recently returned line, blank lines removed by `LinesOpt::SECTIONS` start
+    // a new one
+    pub fn section(&self) -> usize {
+        self.section
+    }
+
+    // Same as `Lines::sections`, split where blank lines were removed or at blank lines
+    pub fn sections(mut self) -> impl Iterator<Item = Vec<&'a [u8]>> {
+        let mut pending: Option<&'a [u8]> = None;
+        std::iter::from_fn(move || {
+            let mut lines = Vec::new();
+            let mut section = None;
+            while let Some(line) = pending.take().or_else(|| self.next()) {
+                if is_blank(line) {
+                    if lines.is_empty() {
+                        continue;
+                    }
+                    break;
+                }
+                match section {
+                    None => section = Some(self.section),
+                    Some(section) if section != self.section => {
+                        pending = Some(line);
+                        break;
+                    }
+                    Some(_) => {}
+                }
+                lines.push(line);
+            }
+            if lines.is_empty() {
+                None
+            } else {
+                Some(lines)
+            }
+        })
+    }
+
+    pub fn strs(self) -> impl Iterator<Item = Result<&'a str, Error>> {
+        let mut lines = self;
+        std::iter::from_fn(move || {
+            let line = lines.next()?;
+            Some(
+                std::str::from_utf8(line)
+                    .at_line(lines.line_number(), &String::from_utf8_lossy(line)),
+            )
+        })
+    }
+
+    pub fn numbers<T: Integer>(self) -> impl Iterator<Item = Result<Vec<T>, Error>> + 'a {
+        let mut lines = self;
+        std::iter::from_fn(move || {
+            let line = lines.next()?;
+            Some(
+                Numbers::new(line)
+                    .collect::<Result<Vec<T>, Error>>()
+                    .at_line(lines.line_number(), &String::from_utf8_lossy(line)),
+            )
+        })
+    }
+}
+
+impl<'a> Iterator for ByteLines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.rest.is_empty() {
+            let mut line = match self.rest.iter().position(|b| *b == b'\n') {
+                Some(end) => {
+                    let line = &self.rest[..end];
+                    self.rest = &self.rest[end + 1..];
+                    line
+                }
+                None => std::mem::take(&mut self.rest),
+            };
+            self.number += 1;
+
+            if let Some(l) = line.strip_suffix(b"\r") {
+                line = l;
+            }
+            let Some(line) = self.options.clean_bytes(line) else {
+                continue;
+            };
+            if self.options.contains(LinesOpt::SECTIONS) && is_blank(line) {
+                // Runs of blank lines only start one new section
+                self.blank = self.returned;
+                continue;
+            }
+            if self.options.contains(LinesOpt::REMOVE_EMPTY) && line.is_empty() {
+                continue;
+            }
+            if std::mem::take(&mut self.blank) {
+                self.section += 1;
+            }
+            self.returned = true;
+            return Some(line);
+        }
+        None
+    }
+}
+
+fn parse_digits<T: Integer>(digits: &[u8], negative: bool) -> Result<T, Error> {
+    let ten = T::from_u8(10);
+    let mut v = T::ZERO;
+    for d in digits {
+        let d = T::from_u8(d - b'0');
+        v = v
+            .checked_mul(ten)
+            .and_then(|v| {
+                if negative {
+                    v.checked_sub(d)
+                } else {
+                    v.checked_add(d)
+                }
+            })
+            .ok_or_else(|| {
+                Error::InvalidInput(format!(
+                    "{sign}{digits} does not fit in {ty}",
+                    sign = if negative { "-" } else { "" },
+                    digits = String::from_utf8_lossy(digits),
+                    ty = std::any::type_name::<T>()
+                ))
+            })?;
+    }
+    Ok(v)
+}
+
+// A whole field, with an optional sign
+pub fn parse_integer<T: Integer>(bytes: &[u8]) -> Result<T, Error> {
+    let (negative, digits) = match bytes.first() {
+        Some(b'-') => (true, &bytes[1..]),
+        Some(b'+') => (false, &bytes[1..]),
+        _ => (false, bytes),
+    };
+    if digits.is_empty() {
+        return Err(Error::InvalidInput("Empty integer".into()));
+    }
+    if let Some(idx) = digits.iter().position(|b| !b.is_ascii_digit()) {
+        let column = idx + bytes.len() - digits.len() + 1;
+        return Err(Error::InvalidInput(format!(
+            "Invalid integer {:?}",
+            String::from_utf8_lossy(bytes)
+        )))
+        .at_column(column);
+    }
+    parse_digits(digits, negative)
+}
+
+// Every integer in `bytes`.  A '-' directly before a digit is a sign unless it follows a digit.
+pub struct Numbers<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T> Numbers<'a, T> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Integer> Iterator for Numbers<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+        while self.pos < bytes.len() {
+            let start = self.pos;
+            let negative = bytes[start] == b'-'
+                && bytes.get(start + 1).is_some_and(u8::is_ascii_digit)
+                && (start == 0 || !bytes[start - 1].is_ascii_digit());
+            if !negative && !bytes[start].is_ascii_digit() {
+                self.pos += 1;
+                continue;
+            }
+
+            let digits_start = if negative { start + 1 } else { start };
+            self.pos = digits_start;
+            while self.pos < bytes.len() && bytes[self.pos].is_ascii_digit() {
+                self.pos += 1;
+            }
+            return Some(
+                parse_digits(&bytes[digits_start..self.pos], negative).at_column(start + 1),
+            );
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lines;
+
+    const INPUT: &str = " # Hello\r\n\r\nWorld\n  \n# Todo \nYup  ";
+
+    #[test]
+    fn same_as_lines() -> Result<(), Error> {
+        for options in [
+            LinesOpt::RAW,
+            LinesOpt::TRIM,
+            LinesOpt::REMOVE_COMMENTS,
+            LinesOpt::REMOVE_EMPTY,
+            LinesOpt::TRIM | LinesOpt::REMOVE_COMMENTS,
+            LinesOpt::TRIM | LinesOpt::REMOVE_EMPTY,
+            LinesOpt::ALL,
+        ] {
+            let lines = Lines::from_bufread(INPUT.as_bytes(), options)?;
+            let byte_lines: Vec<&str> = ByteLines::new(INPUT.as_bytes(), options)?
+                .strs()
+                .collect::<Result<_, _>>()?;
+            assert_eq!(byte_lines, lines.iter().collect::<Vec<&str>>());
+        }
+        assert!(ByteLines::new(INPUT.as_bytes(), LinesOpt::new().expand_tabs(4)).is_err());
+
+        // Unicode whitespace, including a vertical tab, is trimmed
+        let input = "\u{a0}a\u{2003}\n\x0bb\x0b\n c ";
+        let lines = Lines::from_bufread(input.as_bytes(), LinesOpt::TRIM)?;
+        let byte_lines: Vec<&str> = ByteLines::new(input.as_bytes(), LinesOpt::TRIM)?
+            .strs()
+            .collect::<Result<_, _>>()?;
+        assert_eq!(byte_lines, lines.iter().collect::<Vec<&str>>());
+        let invalid = b" \xff c \xfe";
+        assert_eq!(
+            ByteLines::new(invalid, LinesOpt::TRIM)?.next(),
+            Some(&b"\xff c \xfe"[..])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sections() -> Result<(), Error> {
+        let input = "\n a\nb\n \n\u{a0}\n# c\nd\n\n\n e\n\n";
+        for options in [
+            LinesOpt::RAW,
+            LinesOpt::SECTIONS,
+            LinesOpt::SECTIONS | LinesOpt::REMOVE_COMMENTS,
+            LinesOpt::ALL,
+        ] {
+            let lines = Lines::from_bufread(input.as_bytes(), options)?;
+            let expected: Vec<Vec<&[u8]>> = lines
+                .sections()
+                .map(|s| s.iter().map(str::as_bytes).collect())
+                .collect();
+            let sections: Vec<Vec<&[u8]>> = ByteLines::new(input.as_bytes(), options)?
+                .sections()
+                .collect();
+            assert_eq!(sections, expected, "{options:?}");
+        }
+
+        let mut lines = ByteLines::new(input.as_bytes(), LinesOpt::ALL)?;
+        let sections: Vec<(usize, &[u8])> = std::iter::from_fn(|| {
+            let line = lines.next()?;
+            Some((lines.section(), line))
+        })
+        .collect();
+        assert_eq!(sections, [(0, &b"a"[..]), (0, b"b"), (1, b"d"), (2, b"e")]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn numbers() -> Result<(), Error> {
+        let input = b"x=2, y=-18\n\n1-3 a\n-0";
+        let numbers: Vec<Vec<i32>> = ByteLines::new(input, LinesOpt::REMOVE_EMPTY)?
+            .numbers()
+            .collect::<Result<_, _>>()?;
+        assert_eq!(numbers, [vec![2, -18], vec![1, 3], vec![0]]);
+
+        let err = ByteLines::new(input, LinesOpt::REMOVE_EMPTY)?
+            .numbers::<u8>()
+            .find_map(|r| r.err())
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 8: Invalid input: -18 does not fit in u8"
+        );
+
+        assert_eq!(
+            Numbers::<i8>::new(b"127 -128").collect::<Result<Vec<_>, _>>()?,
+            [127, -128]
+        );
+        assert!(Numbers::<i8>::new(b"128").next().unwrap().is_err());
+        assert_eq!(
+            Numbers::<u64>::new(b"18446744073709551615")
+                .next()
+                .unwrap()?,
+            u64::MAX
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn integer() -> Result<(), Error> {
+        assert_eq!(parse_integer::<isize>(b"-42")?, -42);
+        assert_eq!(parse_integer::<usize>(b"+0042")?, 42);
+        assert!(parse_integer::<usize>(b"").is_err());
+        let err = parse_integer::<usize>(b"12a").unwrap_err();
+        assert_eq!(err.position().unwrap().column, Some(3));
+
+        Ok(())
+    }
+}
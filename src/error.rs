@@ -1,9 +1,12 @@
 use super::file_scanner::InputFile;
 use std::ffi::OsString;
+use std::fmt::Display;
 use std::num::{ParseFloatError, ParseIntError};
 
 #[derive(Debug)]
 pub enum Error {
+    AtInput(InputPosition, Box<Error>),
+    Context(String, Box<Error>),
     DuplicateInputFile(InputFile),
     Fmt(std::fmt::Error),
     IO(std::io::Error),
@@ -25,6 +28,165 @@ pub enum Error {
     MD5StringOverrun,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputPosition {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: Option<String>,
+}
+
+impl Display for InputPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}"),
+            (Some(line), None) => write!(f, "line {line}"),
+            (None, Some(column)) => write!(f, "column {column}"),
+            (None, None) => write!(f, "unknown position"),
+        }
+    }
+}
+
+impl Error {
+    pub fn context(self, msg: impl Display) -> Self {
+        Self::Context(msg.to_string(), Box::new(self))
+    }
+
+    // Line and column are 1 based.  An existing position without a line is filled in.
+    pub fn at_line(self, line: usize, text: &str) -> Self {
+        match self {
+            Self::AtInput(mut pos, e) if pos.line.is_none() => {
+                pos.line = Some(line);
+                pos.text = Some(text.into());
+                Self::AtInput(pos, e)
+            }
+            Self::AtInput(..) => self,
+            e => Self::AtInput(
+                InputPosition {
+                    line: Some(line),
+                    column: None,
+                    text: Some(text.into()),
+                },
+                Box::new(e),
+            ),
+        }
+    }
+
+    pub fn at_column(self, column: usize) -> Self {
+        match self {
+            Self::AtInput(mut pos, e) if pos.column.is_none() => {
+                pos.column = Some(column);
+                Self::AtInput(pos, e)
+            }
+            Self::AtInput(..) => self,
+            e => Self::AtInput(
+                InputPosition {
+                    column: Some(column),
+                    ..Default::default()
+                },
+                Box::new(e),
+            ),
+        }
+    }
+
+    pub fn position(&self) -> Option<&InputPosition> {
+        match self {
+            Self::AtInput(pos, _) => Some(pos),
+            Self::Context(_, e) => e.position(),
+            _ => None,
+        }
+    }
+    // Sources not already part of the message.  Every `Error` displays its source, so the chain
+    // starts after the first source that isn't an `Error`.
+    pub fn causes(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        let mut source = std::error::Error::source(self);
+        while let Some(e) = source.and_then(|e| e.downcast_ref::<Error>()) {
+            source = std::error::Error::source(e);
+        }
+        std::iter::successors(source.and_then(|e| e.source()), |e| e.source())
+    }
+}
+
+pub trait ErrorContext<T> {
+    fn context(self, msg: impl Display) -> Result<T, Error>;
+    fn at_line(self, line: usize, text: &str) -> Result<T, Error>;
+    fn at_column(self, column: usize) -> Result<T, Error>;
+}
+
+impl<T, E: Into<Error>> ErrorContext<T> for Result<T, E> {
+    fn context(self, msg: impl Display) -> Result<T, Error> {
+        self.map_err(|e| e.into().context(msg))
+    }
+
+    fn at_line(self, line: usize, text: &str) -> Result<T, Error> {
+        self.map_err(|e| e.into().at_line(line, text))
+    }
+
+    fn at_column(self, column: usize) -> Result<T, Error> {
+        self.map_err(|e| e.into().at_column(column))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AtInput(pos, e) => write!(f, "{pos}: {e}"),
+            Self::Context(msg, e) => write!(f, "{msg}: {e}"),
+            Self::DuplicateInputFile(input_file) => write!(
+                f,
+                "Duplicate input file {:?}, remove or rename one of the copies",
+                input_file.path()
+            ),
+            Self::Fmt(e) => write!(f, "Formatting error: {e}"),
+            Self::IO(e) => write!(f, "I/O error: {e}"),
+            Self::InvalidInput(s) => write!(f, "Invalid input: {s}"),
+            Self::InvalidInputFile(name) => write!(f, "Invalid input file name {name:?}"),
+            Self::MinReq(e) => write!(f, "Request failed: {e}"),
+            Self::MissingCookies => write!(
+                f,
+                "Missing session cookie, save it as \"session=...\" in cookies.txt"
+            ),
+            Self::MissingExpect(output) => write!(f, "No expected output for answer {output}"),
+            Self::MissingInput => write!(f, "No input file found in input_files"),
+            Self::ParseFloatError(e) => write!(f, "Could not parse float: {e}"),
+            Self::ParseIntError(e) => write!(f, "Could not parse integer: {e}"),
+            Self::Runner(s) => write!(f, "{s}"),
+            Self::SearchUpFailed(name) => write!(
+                f,
+                "Could not find {name:?} in the current directory or any parent directory"
+            ),
+            Self::Skipped => write!(f, "Skipped"),
+            Self::Unsolved => write!(f, "Unsolved"),
+            Self::Utf8Error(e) => write!(f, "Invalid UTF-8: {e}"),
+            Self::WrongAnswer(output, expect) => {
+                write!(f, "Wrong answer {output}, expected {expect}")
+            }
+            Self::YearExists(year) => write!(f, "Year {year} already exists"),
+            Self::MD5StringOverrun => write!(f, "MD5String capacity exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::AtInput(_, e) | Self::Context(_, e) => Some(e.as_ref()),
+            Self::Fmt(e) => Some(e),
+            Self::IO(e) => Some(e),
+            Self::MinReq(e) => Some(e),
+            Self::ParseFloatError(e) => Some(e),
+            Self::ParseIntError(e) => Some(e),
+            Self::Utf8Error(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::convert::Infallible> for Error {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
 impl From<std::str::Utf8Error> for Error {
     fn from(e: std::str::Utf8Error) -> Self {
         Self::Utf8Error(e)
@@ -60,3 +222,36 @@ impl From<ParseFloatError> for Error {
         Self::ParseFloatError(e)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Error;
+
+    #[test]
+    fn display_and_source() {
+        let e: Error = "x".parse::<usize>().unwrap_err().into();
+        assert_eq!(
+            e.to_string(),
+            "Could not parse integer: invalid digit found in string"
+        );
+        assert!(std::error::Error::source(&e).is_some());
+
+        let e: Box<dyn std::error::Error> = Box::new(Error::SearchUpFailed("input_files".into()));
+        assert_eq!(
+            e.to_string(),
+            "Could not find \"input_files\" in the current directory or any parent directory"
+        );
+        assert!(e.source().is_none());
+    }
+
+    #[test]
+    fn causes() {
+        let e: Error = "x".parse::<usize>().unwrap_err().into();
+        let e = e.at_line(4, "x").context("Parsing");
+        assert_eq!(
+            e.to_string(),
+            "Parsing: line 4: Could not parse integer: invalid digit found in string"
+        );
+        assert_eq!(e.causes().count(), 0);
+    }
+}
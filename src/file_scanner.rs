@@ -232,7 +232,11 @@ pub fn download_input(year: usize, day: usize) -> Result<(), Error> {
         return Ok(());
     }
     let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-    let cookies_path = search_up("cookies.txt", SearchType::File)?;
+    let cookies_path = match search_up("cookies.txt", SearchType::File) {
+        Ok(cookies_path) => cookies_path,
+        Err(Error::SearchUpFailed(_)) => return Err(Error::MissingCookies),
+        Err(e) => return Err(e),
+    };
     let cookies = std::fs::read_to_string(cookies_path)?;
     let response = minreq::get(url).with_header("Cookie", cookies).send()?;
 
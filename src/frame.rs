@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    lines: Vec<String>,
+}
+
+pub trait Render {
+    fn render(&self) -> Frame;
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_fn<F>(min: (isize, isize), max: (isize, isize), f: F) -> Self
+    where
+        F: Fn(isize, isize) -> char,
+    {
+        let lines = (min.1..=max.1)
+            .map(|y| (min.0..=max.0).map(|x| f(x, y)).collect())
+            .collect();
+        Self { lines }
+    }
+
+    pub fn push_line(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self.lines.iter() {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Render for Frame {
+    fn render(&self) -> Frame {
+        self.clone()
+    }
+}
+
+impl Render for [String] {
+    fn render(&self) -> Frame {
+        Frame {
+            lines: self.to_vec(),
+        }
+    }
+}
+
+impl Render for Vec<String> {
+    fn render(&self) -> Frame {
+        self.as_slice().render()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BitGrid, BitGridConst};
+
+    #[test]
+    fn from_fn() {
+        let frame = Frame::from_fn((-1, 0), (1, 1), |x, y| if x == y { '#' } else { '.' });
+        assert_eq!(frame.lines(), [".#.", "..#"].map(String::from));
+        assert_eq!(frame.to_string(), ".#.\n..#\n");
+    }
+
+    #[test]
+    fn render_bit_grid() {
+        let mut grid: BitGrid = BitGrid::new();
+        assert_eq!(grid.render(), Frame::new());
+
+        grid.set_bit(-2, 5);
+        grid.set_bit(0, 6);
+        assert_eq!(grid.render().to_string(), "#..\n..#\n");
+
+        let mut grid: BitGridConst<-1, -1, 64, 3> = BitGridConst::new();
+        grid.set_bit(0, 0);
+        grid.set_bit(62, 1);
+        let frame = grid.render();
+        assert_eq!(frame.lines().len(), 3);
+        assert_eq!(&frame.lines()[1][..3], ".#.");
+        assert_eq!(&frame.lines()[2][62..], ".#");
+    }
+}
@@ -0,0 +1,715 @@
+use crate::{
+    Connectivity, Error, ErrorContext, Frame, Integer, NumberedLines, Point2D, Region, Render,
+};
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+// What happens to neighbours and steps that leave the grid
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum Edges {
+    // They are dropped
+    #[default]
+    Clamped,
+    // They wrap around modulo width and height
+    Wrapping,
+}
+
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    edges: Edges,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+            edges: Edges::default(),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Result<Self, Error> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return Err(Error::InvalidInput(format!(
+                "{} cells do not fill rows of width {width}",
+                cells.len()
+            )));
+        }
+        Ok(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+            edges: Edges::default(),
+        })
+    }
+
+    pub fn from_lines<'a, F>(lines: impl NumberedLines<'a>, mut f: F) -> Result<Self, Error>
+    where
+        F: FnMut(char) -> T,
+    {
+        Self::try_from_lines(lines, |c| Ok::<T, Error>(f(c)))
+    }
+
+    // Every line must be the same width.  Errors from `f` report the line and column of the char.
+    pub fn try_from_lines<'a, E, F>(lines: impl NumberedLines<'a>, mut f: F) -> Result<Self, Error>
+    where
+        E: Into<Error>,
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (number, line) in lines.numbered_lines() {
+            let start = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                cells.push(f(c).at_column(column + 1).at_line(number, line)?);
+            }
+            let len = cells.len() - start;
+            if *width.get_or_insert(len) != len {
+                return Err(Error::InvalidInput(format!(
+                    "Expected {} cells, got {len}",
+                    width.unwrap()
+                )))
+                .at_line(number, line);
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+            edges: Edges::default(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds<I: Integer>(&self, p: Point2D<I>) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    fn index_of<I: Integer>(&self, p: Point2D<I>) -> Option<usize> {
+        let (x, y) = (p.x.as_isize(), p.y.as_isize());
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            None
+        } else {
+            Some(y as usize * self.width + x as usize)
+        }
+    }
+
+    fn point<I: Integer>(&self, idx: usize) -> Point2D<I> {
+        Point2D::new(
+            I::from_usize(idx % self.width),
+            I::from_usize(idx / self.width),
+        )
+    }
+
+    pub fn get<I: Integer>(&self, p: Point2D<I>) -> Option<&T> {
+        self.index_of(p).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut<I: Integer>(&mut self, p: Point2D<I>) -> Option<&mut T> {
+        self.index_of(p).map(|idx| &mut self.cells[idx])
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    pub fn set_edges(&mut self, edges: Edges) {
+        self.edges = edges;
+    }
+
+    // `p` moved by `delta`, wrapped or dropped depending on `edges`.  An empty grid has nowhere to
+    // step to.
+    pub fn step<I: Integer>(&self, p: Point2D<I>, delta: Point2D<isize>) -> Option<Point2D<I>> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let moved = Point2D::new(p.x.as_isize() + delta.x, p.y.as_isize() + delta.y);
+        let moved = match self.edges {
+            Edges::Clamped if !self.in_bounds(moved) => return None,
+            Edges::Clamped => moved,
+            Edges::Wrapping => {
+                moved.rem_euclid(Point2D::new(self.width as isize, self.height as isize))
+            }
+        };
+        Some(Point2D::new(
+            I::from_usize(moved.x as usize),
+            I::from_usize(moved.y as usize),
+        ))
+    }
+
+    pub fn neighbors<'a, I: Integer + 'a>(
+        &'a self,
+        p: Point2D<I>,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = Point2D<I>> + 'a {
+        connectivity
+            .neighbors(Point2D::default())
+            .filter_map(move |delta| self.step(p, delta))
+    }
+
+    pub fn cardinal_neighbors<'a, I: Integer + 'a>(
+        &'a self,
+        p: Point2D<I>,
+    ) -> impl Iterator<Item = Point2D<I>> + 'a {
+        self.neighbors(p, Connectivity::Four)
+    }
+
+    pub fn all_neighbors<'a, I: Integer + 'a>(
+        &'a self,
+        p: Point2D<I>,
+    ) -> impl Iterator<Item = Point2D<I>> + 'a {
+        self.neighbors(p, Connectivity::Eight)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks_exact panics on 0
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2D<usize>, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, c)| (self.point(idx), c))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point2D<usize>, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(idx, c)| (Point2D::new(idx % width, idx / width), c))
+    }
+
+    pub fn points<'a, I: Integer + 'a>(&'a self) -> impl Iterator<Item = Point2D<I>> + 'a {
+        (0..self.cells.len()).map(|idx| self.point(idx))
+    }
+
+    pub fn find<I: Integer>(&self, v: &T) -> Option<Point2D<I>>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .position(|c| c == v)
+            .map(|idx| self.point(idx))
+    }
+
+    pub fn find_all<'a, I: Integer + 'a>(
+        &'a self,
+        v: &'a T,
+    ) -> impl Iterator<Item = Point2D<I>> + 'a
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, c)| *c == v)
+            .map(|(idx, _)| self.point(idx))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            edges: self.edges,
+        }
+    }
+}
+
+impl<T, I: Integer> Index<Point2D<I>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2D<I>) -> &Self::Output {
+        match self.index_of(p) {
+            Some(idx) => &self.cells[idx],
+            None => panic!("{p} out of bounds for {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T, I: Integer> IndexMut<Point2D<I>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2D<I>) -> &mut Self::Output {
+        match self.index_of(p) {
+            Some(idx) => &mut self.cells[idx],
+            None => panic!("{p} out of bounds for {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    // Cells with the same value as `start` that are connected to it
+    pub fn flood_fill<I: Integer>(&self, start: Point2D<I>, connectivity: Connectivity) -> Region {
+        let mut seen = vec![false; self.cells.len()];
+        self.fill_unseen(
+            start.x.as_isize(),
+            start.y.as_isize(),
+            connectivity,
+            &mut seen,
+        )
+    }
+
+    pub fn regions(&self, connectivity: Connectivity) -> Vec<Region> {
+        let mut seen = vec![false; self.cells.len()];
+        let mut regions = Vec::new();
+        for idx in 0..self.cells.len() {
+            if !seen[idx] {
+                let p: Point2D<isize> = self.point(idx);
+                regions.push(self.fill_unseen(p.x, p.y, connectivity, &mut seen));
+            }
+        }
+        regions
+    }
+
+    fn fill_unseen(
+        &self,
+        x: isize,
+        y: isize,
+        connectivity: Connectivity,
+        seen: &mut [bool],
+    ) -> Region {
+        let Some(value) = self.get(Point2D::new(x, y)) else {
+            return Region::default();
+        };
+        Region::flood_fill(
+            Point2D::new(x, y),
+            |p| self.neighbors(p, connectivity),
+            |p| match self.index_of(p) {
+                Some(idx) if !seen[idx] && self.cells[idx] == *value => {
+                    seen[idx] = true;
+                    true
+                }
+                _ => false,
+            },
+        )
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        Self {
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|idx| f(idx % width, idx / width))
+                .collect(),
+            edges: Edges::default(),
+        }
+    }
+
+    fn transformed<F: FnMut(usize, usize) -> T>(&self, width: usize, height: usize, f: F) -> Self {
+        Self {
+            edges: self.edges,
+            ..Self::from_fn(width, height, f)
+        }
+    }
+
+    fn at(&self, x: usize, y: usize) -> T {
+        self.cells[y * self.width + x].clone()
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.transformed(self.height, self.width, |x, y| self.at(y, x))
+    }
+
+    // Mirror left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.transformed(self.width, self.height, |x, y| {
+            self.at(self.width - 1 - x, y)
+        })
+    }
+
+    // Mirror top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.transformed(self.width, self.height, |x, y| {
+            self.at(x, self.height - 1 - y)
+        })
+    }
+
+    // Clockwise, with y increasing downwards
+    pub fn rotate_90(&self) -> Self {
+        self.transformed(self.height, self.width, |x, y| {
+            self.at(y, self.height - 1 - x)
+        })
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.transformed(self.width, self.height, |x, y| {
+            self.at(self.width - 1 - x, self.height - 1 - y)
+        })
+    }
+
+    pub fn rotate_270(&self) -> Self {
+        self.transformed(self.height, self.width, |x, y| {
+            self.at(self.width - 1 - y, x)
+        })
+    }
+
+    // The 4 rotations, followed by the 4 rotations of the horizontal flip
+    pub fn symmetries(&self) -> [Self; 8] {
+        let flipped = self.flip_horizontal();
+        [
+            self.clone(),
+            self.rotate_90(),
+            self.rotate_180(),
+            self.rotate_270(),
+            flipped.clone(),
+            flipped.rotate_90(),
+            flipped.rotate_180(),
+            flipped.rotate_270(),
+        ]
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn view(&self, min: Point2D<usize>, width: usize, height: usize) -> GridView<'_, T> {
+        assert!(
+            min.x + width <= self.width && min.y + height <= self.height,
+            "{width}x{height} view at {min} out of bounds for {}x{} grid",
+            self.width,
+            self.height
+        );
+        GridView {
+            grid: self,
+            min,
+            width,
+            height,
+        }
+    }
+}
+
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    min: Point2D<usize>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get<I: Integer>(&self, p: Point2D<I>) -> Option<&'a T> {
+        let (x, y) = (p.x.as_isize(), p.y.as_isize());
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            None
+        } else {
+            Some(&self.row(y as usize)[x as usize])
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.grid.row(self.min.y + y)[self.min.x..self.min.x + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &'a T> + '_ {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.rows().map(move |row| &row[x])
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.rows().flatten().cloned().collect(),
+            edges: self.grid.edges,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for GridView<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.rows().eq(other.rows())
+    }
+}
+
+impl<T, I: Integer> Index<Point2D<I>> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, p: Point2D<I>) -> &Self::Output {
+        match self.get(p) {
+            Some(v) => v,
+            None => panic!("{p} out of bounds for {}x{} view", self.width, self.height),
+        }
+    }
+}
+
+impl<T: Display> Render for Grid<T> {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::new();
+        for row in self.rows() {
+            frame.push_line(row.iter().map(|c| c.to_string()).collect::<String>());
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lines, LinesOpt};
+
+    const INPUT: &str = "#.#\n.S.\n9#.";
+
+    fn grid() -> Grid<char> {
+        let lines = Lines::from_bufread(INPUT.as_bytes(), LinesOpt::RAW).unwrap();
+        Grid::from_lines(lines.iter(), |c| c).unwrap()
+    }
+
+    #[test]
+    fn from_lines() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.render().to_string(), format!("{INPUT}\n"));
+
+        let err = Grid::from_lines(["ab", "abc"], |c| c).unwrap_err();
+        assert_eq!(err.position().unwrap().line, Some(2));
+
+        let err = Grid::try_from_lines(["12", "3x"], |c| {
+            c.to_digit(10)
+                .ok_or_else(|| Error::InvalidInput(format!("{c} is not a digit")))
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: Invalid input: x is not a digit"
+        );
+
+        // Line numbers are from the input, not counting the lines that were removed
+        let lines = Lines::from_bufread("# c\n\nab\nax\n\nabc".as_bytes(), LinesOpt::ALL).unwrap();
+        let err = Grid::try_from_lines(lines.sections().next().unwrap(), |c| match c {
+            'x' => Err(Error::InvalidInput("x".to_string())),
+            c => Ok(c),
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "line 4, column 2: Invalid input: x");
+        let err = Grid::from_lines(&lines, |c| c).unwrap_err();
+        assert_eq!(err.position().unwrap().line, Some(6));
+
+        let digits = Grid::try_from_lines(["12", "34"], |c| c.to_string().parse::<u8>()).unwrap();
+        assert_eq!(digits.cells(), [1, 2, 3, 4]);
+        assert!(Grid::from_vec(3, vec![0; 4]).is_err());
+    }
+
+    #[test]
+    fn access() {
+        let mut grid = grid();
+        let start: Point2D<isize> = grid.find(&'S').unwrap();
+        assert_eq!(start, Point2D::new(1, 1));
+        assert_eq!(grid[start], 'S');
+        assert_eq!(grid.get(Point2D::new(-1isize, 0)), None);
+        assert_eq!(grid.get(Point2D::new(3usize, 0)), None);
+        assert_eq!(grid.get(Point2D::new(0usize, 2)), Some(&'9'));
+        grid[Point2D::new(1u8, 2)] = '.';
+        *grid.get_mut(Point2D::new(0usize, 0)).unwrap() = '.';
+        assert_eq!(grid.find_all::<usize>(&'#').count(), 1);
+
+        assert_eq!(grid.row(1), ['.', 'S', '.']);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.column(0).collect::<String>(), "..9");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["..9", ".S.", "#.."]
+        );
+        assert_eq!(
+            grid.map(|c| *c == '.').iter().filter(|(_, c)| **c).count(),
+            6
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = grid();
+        let corner = Point2D::<usize>::new(0, 0);
+        assert_eq!(
+            grid.cardinal_neighbors(corner).collect::<Vec<_>>(),
+            [Point2D::new(1, 0), Point2D::new(0, 1)]
+        );
+        assert_eq!(grid.all_neighbors(corner).count(), 3);
+        assert_eq!(grid.all_neighbors(Point2D::<isize>::new(1, 1)).count(), 8);
+        assert_eq!(
+            grid.cardinal_neighbors(Point2D::<i32>::new(2, 2)).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn transform() {
+        let grid = Grid::from_lines(["abc", "def"], |c| c).unwrap();
+        let text =
+            |g: &Grid<char>| -> Vec<String> { g.rows().map(|r| r.iter().collect()).collect() };
+        assert_eq!(text(&grid.transpose()), ["ad", "be", "cf"]);
+        assert_eq!(text(&grid.flip_horizontal()), ["cba", "fed"]);
+        assert_eq!(text(&grid.flip_vertical()), ["def", "abc"]);
+        assert_eq!(text(&grid.rotate_90()), ["da", "eb", "fc"]);
+        assert_eq!(text(&grid.rotate_180()), ["fed", "cba"]);
+        assert_eq!(text(&grid.rotate_270()), ["cf", "be", "ad"]);
+        assert_eq!(grid.rotate_90().rotate_270(), grid);
+
+        let symmetries = grid.symmetries();
+        assert_eq!(symmetries[0], grid);
+        assert_eq!(symmetries[4], grid.flip_horizontal());
+        for i in 1..4 {
+            assert_eq!(symmetries[i], symmetries[i - 1].rotate_90());
+            assert_eq!(symmetries[i + 4], symmetries[i + 3].rotate_90());
+        }
+        for (i, a) in symmetries.iter().enumerate() {
+            assert!(symmetries[i + 1..].iter().all(|b| a != b));
+        }
+        assert!(symmetries.contains(&grid.transpose()));
+        assert!(symmetries.contains(&grid.flip_vertical()));
+    }
+
+    #[test]
+    fn view() {
+        let grid = Grid::from_fn(5, 4, |x, y| y * 10 + x);
+        let view = grid.view(Point2D::new(1, 2), 3, 2);
+        assert_eq!((view.width(), view.height()), (3, 2));
+        assert_eq!(view.row(1), [31, 32, 33]);
+        assert_eq!(view[Point2D::new(0usize, 0)], 21);
+        assert_eq!(view.get(Point2D::new(3usize, 0)), None);
+        assert_eq!(view.column(2).copied().collect::<Vec<_>>(), [23, 33]);
+        assert_eq!(view.to_grid().cells(), [21, 22, 23, 31, 32, 33]);
+
+        let other = Grid::from_fn(3, 2, |x, y| (y + 2) * 10 + x + 1);
+        assert!(view == other.view(Point2D::new(0, 0), 3, 2));
+        assert!(view != grid.view(Point2D::new(0, 0), 3, 2));
+    }
+
+    #[test]
+    fn regions() {
+        let price = |lines: &[&str]| -> (usize, usize) {
+            let grid = Grid::from_lines(lines, |c| c).unwrap();
+            grid.regions(Connectivity::Four)
+                .iter()
+                .map(|r| (r.area() * r.perimeter(), r.area() * r.sides()))
+                .fold((0, 0), |a, b| (a.0 + b.0, a.1 + b.1))
+        };
+        assert_eq!(price(&["AAAA", "BBCD", "BBCC", "EEEC"]), (140, 80));
+        assert_eq!(price(&["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"]).1, 236);
+        assert_eq!(
+            price(&["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"]).1,
+            368
+        );
+
+        let grid = Grid::from_lines(["#.#", ".#.", "#.."], |c| c).unwrap();
+        assert_eq!(grid.regions(Connectivity::Four).len(), 7);
+        assert_eq!(grid.regions(Connectivity::Eight).len(), 2);
+        let x = grid.flood_fill(Point2D::new(0usize, 0), Connectivity::Eight);
+        assert_eq!(x.area(), 4);
+        assert!(x.contains(Point2D::new(0, 2)));
+        assert_eq!(
+            grid.flood_fill(Point2D::new(3isize, 0), Connectivity::Four)
+                .area(),
+            0
+        );
+
+        // No recursion
+        let grid = Grid::new(1000, 250, 0u8);
+        let regions = grid.regions(Connectivity::Four);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].perimeter(), 2500);
+        assert_eq!(regions[0].sides(), 4);
+    }
+
+    #[test]
+    fn edges() {
+        let mut grid = Grid::from_fn(4, 3, |x, y| y * 4 + x);
+        let corner = Point2D::<usize>::new(3, 0);
+        assert_eq!(grid.step(corner, Point2D::new(1, 0)), None);
+        assert_eq!(grid.cardinal_neighbors(corner).count(), 2);
+
+        grid.set_edges(Edges::Wrapping);
+        assert_eq!(
+            grid.step(corner, Point2D::new(1, 0)),
+            Some(Point2D::new(0, 0))
+        );
+        assert_eq!(
+            grid.step(corner, Point2D::new(-9, -7)),
+            Some(Point2D::new(2, 2))
+        );
+        assert_eq!(
+            grid.cardinal_neighbors(corner).collect::<Vec<_>>(),
+            [
+                Point2D::new(3, 2),
+                Point2D::new(2, 0),
+                Point2D::new(0, 0),
+                Point2D::new(3, 1)
+            ]
+        );
+        assert_eq!(grid.all_neighbors(Point2D::<i8>::new(0, 0)).count(), 8);
+        assert_eq!(grid.transpose().edges(), Edges::Wrapping);
+
+        // Everything is one region when wrapping
+        let grid = Grid::from_lines(["#..#", "....", "#..#"], |c| c).unwrap();
+        let corner = Point2D::<usize>::new(0, 0);
+        assert_eq!(grid.flood_fill(corner, Connectivity::Four).area(), 1);
+        let mut grid = grid;
+        grid.set_edges(Edges::Wrapping);
+        assert_eq!(grid.flood_fill(corner, Connectivity::Four).area(), 4);
+
+        let mut grid = Grid::new(0, 3, 0u8);
+        grid.set_edges(Edges::Wrapping);
+        let origin = Point2D::<usize>::new(0, 0);
+        assert_eq!(grid.step(origin, Point2D::new(1, 0)), None);
+        assert_eq!(grid.all_neighbors(origin).count(), 0);
+    }
+}
@@ -34,6 +34,14 @@ pub trait Integer:
     fn wrapping_mul(self, rhs: Self) -> Self;
     fn wrapping_div(self, rhs: Self) -> Self;
     fn dist(self, rhs: Self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn as_isize(self) -> isize;
+    fn as_usize(self) -> usize;
+    fn from_u8(v: u8) -> Self;
+    fn from_usize(v: usize) -> Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
 }
 
 macro_rules! impl_integer {
@@ -67,6 +75,38 @@ macro_rules! impl_integer {
             fn dist(self, rhs: Self) -> Self {
                 (self - rhs).abs()
             }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                self.rem_euclid(rhs)
+            }
+
+            fn as_isize(self) -> isize {
+                self as isize
+            }
+
+            fn as_usize(self) -> usize {
+                self as usize
+            }
+
+            fn from_u8(v: u8) -> Self {
+                v as Self
+            }
+
+            fn from_usize(v: usize) -> Self {
+                v as Self
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.checked_add(rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.checked_sub(rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                self.checked_mul(rhs)
+            }
         }
     };
     (UNSIGNED => $ty:ty, $sty:ty) => {
@@ -95,6 +135,38 @@ macro_rules! impl_integer {
             fn dist(self, rhs: Self) -> Self {
                 (self as $sty - rhs as $sty).unsigned_abs()
             }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                self.rem_euclid(rhs)
+            }
+
+            fn as_isize(self) -> isize {
+                self as isize
+            }
+
+            fn as_usize(self) -> usize {
+                self as usize
+            }
+
+            fn from_u8(v: u8) -> Self {
+                v as Self
+            }
+
+            fn from_usize(v: usize) -> Self {
+                v as Self
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.checked_add(rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.checked_sub(rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                self.checked_mul(rhs)
+            }
         }
     };
 }
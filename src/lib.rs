@@ -1,17 +1,22 @@
 mod algorithms;
 mod bit_array;
 mod bit_grid;
+mod byte_lines;
 mod dijkstra;
 mod error;
 mod file_scanner;
+mod frame;
+mod grid;
 mod integer;
 mod iter_pairs;
 mod md5;
+mod ocr;
 mod output;
 mod parser;
 mod permutations;
 mod point;
 mod priority_vec;
+mod region;
 mod run_output;
 mod small_vec;
 mod tile_set;
@@ -20,19 +25,28 @@ pub mod new_year;
 pub mod runner;
 
 pub use algorithms::*;
-pub use bit_array::BitArray;
+pub use bit_array::{BitArray, SetBits};
 pub use bit_grid::*;
+pub use byte_lines::{parse_integer, ByteLines, Numbers};
 pub use dijkstra::Dijkstra;
-pub use error::Error;
+pub use error::{Error, ErrorContext, InputPosition};
 pub(crate) use file_scanner::{download_input, search_up, InputFileCache, SearchType};
+pub use frame::{Frame, Render};
+pub use grid::{Edges, Grid, GridView};
 pub use integer::Integer;
 pub use iter_pairs::IterPairs;
 pub use md5::{MD5String, MD5};
-pub use output::{Output, YearDayPart, OUTPUT};
-pub use parser::{Lines, LinesIter, LinesOpt};
+pub use ocr::{ocr, Letters, Pixels};
+pub use output::{
+    output, write_frame, write_output, Level, Output, Visualise, YearDayPart, OUTPUT,
+};
+pub use parser::{
+    numbers, scan, split_fields, FromLine, Lines, LinesIter, LinesOpt, NumberedLines, Scan, Section,
+};
 pub use permutations::{GroupedPermutations, Permutations};
 pub use point::{Point2D, Point3D};
 pub use priority_vec::PriorityVec;
+pub use region::{Connectivity, Region};
 pub use run_output::RunOutput;
 pub use small_vec::SmallVec;
 pub use tile_set::{Tile, TileSet};
@@ -47,36 +61,59 @@ pub trait Runner {
 
 pub type NewRunner = fn() -> Box<dyn Runner>;
 
-pub fn output<F, R>(f: F) -> R
-where
-    F: Fn(&mut Output) -> R,
-{
-    output::OUTPUT.with(|output| f(unsafe { &mut *output.get() }))
-}
-
 #[macro_export]
 macro_rules! print {
     ($($args:tt)*) => {
-        $crate::output(|output| {
-            use std::fmt::Write;
-            let _ = write!(output.mode, $($args)*);
-        });
+        $crate::write_output($crate::Level::Info, format_args!($($args)*));
     };
 }
 
 #[macro_export]
 macro_rules! println {
     () => {
-        $crate::output(|output| {
-            use std::fmt::Write;
-            let _ = writeln!(output.mode);
-        });
+        $crate::write_output($crate::Level::Info, format_args!("\n"));
+    };
+
+    ($($args:tt)*) => {
+        $crate::write_output($crate::Level::Info, format_args!("{}\n", format_args!($($args)*)));
+    };
+}
+
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:expr) => {
+        $crate::scan(&$line, $pattern)
     };
 
+    ($line:expr, $pattern:expr, $($ty:ty),+ $(,)?) => {
+        $crate::scan::<($($ty,)+)>(&$line, $pattern)
+    };
+}
+
+#[macro_export]
+macro_rules! frame {
+    ($grid:expr) => {
+        $crate::write_frame(&$grid);
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($args:tt)*) => {
+        $crate::write_output($crate::Level::Info, format_args!("{}\n", format_args!($($args)*)));
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($args:tt)*) => {
+        $crate::write_output($crate::Level::Debug, format_args!("{}\n", format_args!($($args)*)));
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
     ($($args:tt)*) => {
-        $crate::output(|output| {
-            use std::fmt::Write;
-            let _ = writeln!(output.mode, $($args)*);
-        });
+        $crate::write_output($crate::Level::Trace, format_args!("{}\n", format_args!($($args)*)));
     };
 }
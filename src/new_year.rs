@@ -113,7 +113,7 @@ fn create_year(year: usize, config: Config) -> Result<(), Error> {
         writeln!(d, "#[allow(unused_imports)]")?;
         writeln!(
             d,
-            "use helper::{{print, println, Error, HashMap, HashSet, Lines, LinesOpt}};"
+            "use helper::{{debug, print, println, trace, Error, HashMap, HashSet, Lines, LinesOpt}};"
         )?;
         writeln!(d)?;
         writeln!(d, "#[derive(Default)]")?;
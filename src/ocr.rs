@@ -0,0 +1,321 @@
+use crate::{BitGrid, Error, HashSet, Integer, Point2D};
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.|#..#|#..#|####|#..#|#..#"),
+    ('B', "###.|#..#|###.|#..#|#..#|###."),
+    ('C', ".##.|#..#|#...|#...|#..#|.##."),
+    ('E', "####|#...|###.|#...|#...|####"),
+    ('F', "####|#...|###.|#...|#...|#..."),
+    ('G', ".##.|#..#|#...|#.##|#..#|.###"),
+    ('H', "#..#|#..#|####|#..#|#..#|#..#"),
+    ('I', "###|.#.|.#.|.#.|.#.|###"),
+    ('J', "..##|...#|...#|...#|#..#|.##."),
+    ('K', "#..#|#.#.|##..|#.#.|#.#.|#..#"),
+    ('L', "#...|#...|#...|#...|#...|####"),
+    ('O', ".##.|#..#|#..#|#..#|#..#|.##."),
+    ('P', "###.|#..#|#..#|###.|#...|#..."),
+    ('R', "###.|#..#|#..#|###.|#.#.|#..#"),
+    ('S', ".###|#...|#...|.##.|...#|###."),
+    ('U', "#..#|#..#|#..#|#..#|#..#|.##."),
+    ('Y', "#...#|#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "####|...#|..#.|.#..|#...|####"),
+];
+
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..|.#..#.|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'B',
+        "#####.|#....#|#....#|#....#|#####.|#....#|#....#|#....#|#....#|#####.",
+    ),
+    (
+        'C',
+        ".####.|#....#|#.....|#.....|#.....|#.....|#.....|#.....|#....#|.####.",
+    ),
+    (
+        'E',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'F',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'G',
+        ".####.|#....#|#.....|#.....|#.....|#..###|#....#|#....#|#...##|.###.#",
+    ),
+    (
+        'H',
+        "#....#|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'J',
+        "...###|....#.|....#.|....#.|....#.|....#.|....#.|#...#.|#...#.|.###..",
+    ),
+    (
+        'K',
+        "#....#|#...#.|#..#..|#.#...|##....|##....|#.#...|#..#..|#...#.|#....#",
+    ),
+    (
+        'L',
+        "#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'N',
+        "#....#|##...#|##...#|#.#..#|#.#..#|#..#.#|#..#.#|#...##|#...##|#....#",
+    ),
+    (
+        'P',
+        "#####.|#....#|#....#|#....#|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'R',
+        "#####.|#....#|#....#|#....#|#####.|#..#..|#...#.|#...#.|#....#|#....#",
+    ),
+    (
+        'X',
+        "#....#|#....#|.#..#.|.#..#.|..##..|..##..|.#..#.|.#..#.|#....#|#....#",
+    ),
+    (
+        'Z',
+        "######|.....#|.....#|....#.|...#..|..#...|.#....|#.....|#.....|######",
+    ),
+];
+
+// Rows of pixels covering the bounding box of everything that is lit
+pub trait Pixels {
+    fn pixels(&self) -> Vec<Vec<bool>>;
+}
+
+fn pixels_from_points(points: impl Iterator<Item = (isize, isize)> + Clone) -> Vec<Vec<bool>> {
+    let Some((min, max)) = points.clone().fold(None, |bounds, (x, y)| match bounds {
+        None => Some(((x, y), (x, y))),
+        Some((min, max)) => Some(((x.min(min.0), y.min(min.1)), (x.max(max.0), y.max(max.1)))),
+    }) else {
+        return Vec::new();
+    };
+
+    let width = (max.0 - min.0 + 1) as usize;
+    let height = (max.1 - min.1 + 1) as usize;
+    let mut pixels = vec![vec![false; width]; height];
+    for (x, y) in points {
+        pixels[(y - min.1) as usize][(x - min.0) as usize] = true;
+    }
+    pixels
+}
+
+impl<const BX: usize, const BY: usize> Pixels for BitGrid<BX, BY> {
+    fn pixels(&self) -> Vec<Vec<bool>> {
+        let Some((min, max)) = self.set_bounds() else {
+            return Vec::new();
+        };
+        let points = (min.1..=max.1)
+            .flat_map(|y| (min.0..=max.0).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.bit_is_set(x, y));
+        pixels_from_points(points)
+    }
+}
+
+impl Pixels for [String] {
+    fn pixels(&self) -> Vec<Vec<bool>> {
+        let points = self.iter().enumerate().flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| matches!(c, '#' | '█'))
+                .map(move |(x, _)| (x as isize, y as isize))
+        });
+        pixels_from_points(points)
+    }
+}
+
+impl Pixels for Vec<String> {
+    fn pixels(&self) -> Vec<Vec<bool>> {
+        self.as_slice().pixels()
+    }
+}
+
+impl<T: Integer> Pixels for [Point2D<T>] {
+    fn pixels(&self) -> Vec<Vec<bool>> {
+        pixels_from_points(self.iter().map(|p| (p.x.as_isize(), p.y.as_isize())))
+    }
+}
+
+impl<T: Integer> Pixels for Vec<Point2D<T>> {
+    fn pixels(&self) -> Vec<Vec<bool>> {
+        self.as_slice().pixels()
+    }
+}
+
+impl<T: Integer> Pixels for HashSet<Point2D<T>> {
+    fn pixels(&self) -> Vec<Vec<bool>> {
+        pixels_from_points(self.iter().map(|p| (p.x.as_isize(), p.y.as_isize())))
+    }
+}
+
+impl<T: Integer> Pixels for BTreeSet<Point2D<T>> {
+    fn pixels(&self) -> Vec<Vec<bool>> {
+        pixels_from_points(self.iter().map(|p| (p.x.as_isize(), p.y.as_isize())))
+    }
+}
+
+fn decode(pixels: &[Vec<bool>]) -> Result<String, Error> {
+    let font: &[(char, &str)] = match pixels.len() {
+        6 => &SMALL,
+        10 => &LARGE,
+        height => {
+            return Err(Error::InvalidInput(format!(
+                "Letters must be 6 or 10 pixels high, got {height}"
+            )))
+        }
+    };
+
+    let width = pixels[0].len();
+    let empty_column = |x: usize| pixels.iter().all(|row| !row[x]);
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if empty_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !empty_column(x) {
+            x += 1;
+        }
+        let glyph: Vec<String> = pixels
+            .iter()
+            .map(|row| {
+                row[start..x]
+                    .iter()
+                    .map(|&p| if p { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let glyph = glyph.join("|");
+        match font.iter().find(|(_, g)| *g == glyph) {
+            Some((c, _)) => text.push(*c),
+            None => {
+                return Err(Error::InvalidInput(format!(
+                    "Unknown letter at column {start}: {glyph}"
+                )))
+            }
+        }
+    }
+
+    Ok(text)
+}
+
+pub fn ocr<P: Pixels + ?Sized>(p: &P) -> Result<String, Error> {
+    decode(&p.pixels())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Letters {
+    art: Vec<String>,
+    text: String,
+}
+
+impl Letters {
+    pub fn new<P: Pixels + ?Sized>(p: &P) -> Result<Self, Error> {
+        let pixels = p.pixels();
+        let text = decode(&pixels)?;
+        let art = pixels
+            .iter()
+            .map(|row| row.iter().map(|&p| if p { '#' } else { '.' }).collect())
+            .collect();
+        Ok(Self { art, text })
+    }
+
+    pub fn art(&self) -> &[String] {
+        &self.art
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Display for Letters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self.art.iter() {
+            writeln!(f, "{line}")?;
+        }
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn art(font: &[(char, &str)], text: &str, spacing: usize) -> Vec<String> {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| {
+                font.iter()
+                    .find(|(g, _)| *g == c)
+                    .unwrap()
+                    .1
+                    .split('|')
+                    .collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| g[y])
+                    .collect::<Vec<&str>>()
+                    .join(&".".repeat(spacing))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small_font() {
+        let all: String = SMALL.iter().map(|(c, _)| *c).collect();
+        assert_eq!(ocr(&art(&SMALL, &all, 1)).unwrap(), all);
+
+        // Surrounding blank space is ignored
+        let mut lines = art(&SMALL, "HI", 1);
+        lines.iter_mut().for_each(|l| *l = format!("...{l}.."));
+        lines.insert(0, "".into());
+        assert_eq!(ocr(&lines).unwrap(), "HI");
+    }
+
+    #[test]
+    fn large_font() {
+        let all: String = LARGE.iter().map(|(c, _)| *c).collect();
+        assert_eq!(ocr(&art(&LARGE, &all, 2)).unwrap(), all);
+    }
+
+    #[test]
+    fn from_points() {
+        let lines = art(&SMALL, "ZUG", 1);
+        let mut grid: BitGrid = BitGrid::new();
+        let mut points: Vec<Point2D<isize>> = Vec::new();
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    grid.set_bit(x as isize - 50, y as isize + 7);
+                    points.push(Point2D::new(x as isize, -(y as isize)));
+                }
+            }
+        }
+        assert_eq!(ocr(&grid).unwrap(), "ZUG");
+
+        let letters = Letters::new(&grid).unwrap();
+        assert_eq!(letters.text(), "ZUG");
+        assert_eq!(letters.art(), lines);
+
+        // Upside down points are not letters
+        assert!(ocr(&points).is_err());
+        let points: HashSet<Point2D<isize>> =
+            points.iter().map(|p| Point2D::new(p.x, -p.y)).collect();
+        assert_eq!(ocr(&points).unwrap(), "ZUG");
+    }
+}
@@ -1,8 +1,104 @@
-use std::cell::UnsafeCell;
+use std::cell::Cell;
 use std::fmt::{Display, Write};
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+
+use crate::{Frame, Render};
+
+// Shared by every thread, so threads spawned by a runner use the same prefix and capture
+pub static OUTPUT: Mutex<Output> = Mutex::new(Output::new());
+
+// Mirrors `OUTPUT.mode` not being `Mode::NoOutput`, so `--times` runs never take the lock
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Set when frames will be shown, so frames are not rendered otherwise
+static VISUALISE: AtomicBool = AtomicBool::new(false);
+
+// Partial line written by a thread, only complete lines are written out.  Kept in `Output` so
+// every thread's partial line can be flushed with the run it was written in.
+struct Pending {
+    thread: ThreadId,
+    level: Level,
+    buf: String,
+}
 
 thread_local! {
-    pub static OUTPUT: UnsafeCell<Output> = UnsafeCell::new(Output::default());
+    // Set while this thread is running an `output` closure, which already holds `OUTPUT`
+    static IN_OUTPUT: Cell<bool> = const { Cell::new(false) };
+}
+
+// Clears `IN_OUTPUT` even if the closure panics
+struct InOutput;
+
+impl Drop for InOutput {
+    fn drop(&mut self) {
+        IN_OUTPUT.set(false);
+    }
+}
+
+fn lock() -> MutexGuard<'static, Output> {
+    debug_assert!(
+        !IN_OUTPUT.get(),
+        "OUTPUT is already locked by an `output` closure on this thread"
+    );
+    OUTPUT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// `f` runs with `OUTPUT` locked, so it must not print, draw frames or call `output` itself.  The
+// lock isn't re-entrant, doing so deadlocks (or panics in debug builds).
+pub fn output<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut Output) -> R,
+{
+    let mut output = lock();
+    IN_OUTPUT.set(true);
+    let in_output = InOutput;
+    let r = f(&mut output);
+    drop(in_output);
+    ENABLED.store(!matches!(output.mode, Mode::NoOutput), Ordering::Relaxed);
+    VISUALISE.store(
+        matches!(
+            output.mode,
+            Mode::Stdout {
+                visualise: Some(_),
+                ..
+            }
+        ),
+        Ordering::Relaxed,
+    );
+    r
+}
+
+pub fn write_frame<R: Render + ?Sized>(r: &R) {
+    if !VISUALISE.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let frame = r.render();
+    let mut output = lock();
+    if let Mode::Stdout {
+        ydp,
+        visualise: Some(visualise),
+        ..
+    } = &mut output.mode
+    {
+        let _ = visualise.frame(*ydp, &frame);
+    }
+}
+
+pub fn write_output(level: Level, args: std::fmt::Arguments<'_>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let s = args.to_string();
+    lock().write_pending(level, &s);
 }
 
 #[derive(Copy, Clone, Default)]
@@ -30,9 +126,94 @@ impl Display for YearDayPart {
     }
 }
 
-#[derive(Default)]
+pub enum Visualise {
+    Terminal {
+        interval: Duration,
+        last: Option<Instant>,
+    },
+    Dir {
+        path: PathBuf,
+        next: usize,
+    },
+}
+
+impl Visualise {
+    pub fn terminal(fps: u32) -> Self {
+        Self::Terminal {
+            interval: Duration::from_secs(1) / fps.max(1),
+            last: None,
+        }
+    }
+
+    pub fn dir(path: PathBuf) -> Self {
+        Self::Dir { path, next: 0 }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::Terminal { last, .. } => *last = None,
+            Self::Dir { next, .. } => *next = 0,
+        }
+    }
+
+    fn frame(&mut self, ydp: YearDayPart, frame: &Frame) -> std::io::Result<()> {
+        use std::io::Write;
+        match self {
+            Self::Terminal { interval, last } => {
+                let mut stdout = std::io::stdout().lock();
+                if let Some(last) = last {
+                    std::thread::sleep(interval.saturating_sub(last.elapsed()));
+                } else {
+                    // Clear the screen for the first frame
+                    write!(stdout, "\x1b[2J")?;
+                }
+                write!(stdout, "\x1b[H")?;
+                for line in frame.lines() {
+                    writeln!(stdout, "{line}\x1b[K")?;
+                }
+                write!(stdout, "\x1b[J")?;
+                stdout.flush()?;
+                *last = Some(Instant::now());
+            }
+            Self::Dir { path, next } => {
+                let path = path.join(format!(
+                    "{year}-{day:02}-part{part}-{next:06}.txt",
+                    year = ydp.year,
+                    day = ydp.day,
+                    part = ydp.part
+                ));
+                std::fs::write(path, frame.to_string())?;
+                *next += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn from_verbosity(verbosity: usize) -> Self {
+        match verbosity {
+            0 => Self::Info,
+            1 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
 pub struct Output {
     pub mode: Mode,
+    verbosity: Level,
+    log_dir: Option<PathBuf>,
+    log: Option<File>,
+    pending: Vec<Pending>,
 }
 
 #[derive(Default)]
@@ -42,56 +223,173 @@ pub enum Mode {
     Stdout {
         ydp: YearDayPart,
         new_line: bool,
+        visualise: Option<Visualise>,
     },
     Capture {
         ydp: YearDayPart,
         new_line: bool,
-        capture: String,
+        capture: Vec<(Level, String)>,
     },
 }
 
+impl Default for Output {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Output {
+    pub const fn new() -> Self {
+        Self {
+            mode: Mode::NoOutput,
+            verbosity: Level::Info,
+            log_dir: None,
+            log: None,
+            pending: Vec::new(),
+        }
+    }
+
+    // Partial lines from the previous run are finished off before it ends
     pub fn start_run(&mut self, ydp: YearDayPart) {
+        self.flush_pending();
         self.mode.reset(ydp);
+        self.log = None;
+        if matches!(self.mode, Mode::NoOutput) {
+            return;
+        }
+        if let Some(log_dir) = &self.log_dir {
+            let path = log_dir.join(format!(
+                "{year}-{day:02}-part{part}.log",
+                year = ydp.year,
+                day = ydp.day,
+                part = ydp.part
+            ));
+            self.log = File::create(path).ok();
+        }
     }
 
     pub fn ensure_nl(&mut self) {
+        self.flush_pending();
         self.mode.ensure_nl();
     }
 
     pub fn get_capture(&mut self) -> Option<String> {
-        self.mode.get_capture()
+        self.flush_pending();
+        self.mode.get_capture(self.verbosity)
+    }
+
+    // Buffers `s` for the current thread, writing out any lines it completes
+    pub fn write_pending(&mut self, level: Level, s: &str) {
+        let thread = std::thread::current().id();
+        let idx = match self.pending.iter().position(|p| p.thread == thread) {
+            Some(idx) => idx,
+            None => {
+                self.pending.push(Pending {
+                    thread,
+                    level,
+                    buf: String::new(),
+                });
+                self.pending.len() - 1
+            }
+        };
+
+        let pending = &mut self.pending[idx];
+        if pending.buf.is_empty() {
+            pending.level = level;
+        }
+        pending.buf.push_str(s);
+        let Some(end) = pending.buf.rfind('\n') else {
+            return;
+        };
+        let rest = pending.buf.split_off(end + 1);
+        let lines = std::mem::replace(&mut pending.buf, rest);
+        let level = pending.level;
+        if pending.buf.is_empty() {
+            self.pending.swap_remove(idx);
+        }
+        self.write_level(level, format_args!("{lines}"));
+    }
+
+    // Ends every thread's partial line
+    fn flush_pending(&mut self) {
+        for pending in std::mem::take(&mut self.pending) {
+            self.write_level(pending.level, format_args!("{}\n", pending.buf));
+        }
     }
 
     pub fn stdout(&mut self) {
         self.mode = Mode::Stdout {
             ydp: Default::default(),
             new_line: true,
+            visualise: None,
         };
     }
 
+    // Only takes effect in stdout mode, frames are dropped when capturing or with no output
+    pub fn visualise(&mut self, visualise: Option<Visualise>) {
+        if let Mode::Stdout { visualise: v, .. } = &mut self.mode {
+            *v = visualise;
+        }
+    }
+
     pub fn capture(&mut self) {
         self.mode = Mode::Capture {
             ydp: Default::default(),
             new_line: true,
-            capture: String::new(),
+            capture: Vec::new(),
         };
     }
 
     pub fn no_output(&mut self) {
         self.mode = Mode::NoOutput;
     }
+
+    pub fn verbosity(&mut self, verbosity: Level) {
+        self.verbosity = verbosity;
+    }
+
+    pub fn log_dir(&mut self, log_dir: Option<PathBuf>) {
+        self.log_dir = log_dir;
+    }
+
+    pub fn write_level(&mut self, level: Level, args: std::fmt::Arguments<'_>) {
+        if matches!(self.mode, Mode::NoOutput) {
+            return;
+        }
+        if level > self.verbosity
+            && self.log.is_none()
+            && !matches!(self.mode, Mode::Capture { .. })
+        {
+            return;
+        }
+
+        let s = args.to_string();
+        if let Some(log) = &mut self.log {
+            use std::io::Write;
+            let _ = log.write_all(s.as_bytes());
+        }
+        match &mut self.mode {
+            Mode::Capture { .. } => self.mode.write_level(level, &s),
+            _ if level <= self.verbosity => self.mode.write_level(level, &s),
+            _ => {}
+        }
+    }
 }
 
 impl Mode {
-    pub fn get_capture(&self) -> Option<String> {
+    pub fn get_capture(&self, verbosity: Level) -> Option<String> {
         match self {
             Self::NoOutput | Self::Stdout { .. } => None,
             Self::Capture { capture, .. } => {
+                let capture: String = capture
+                    .iter()
+                    .filter(|(level, _)| *level <= verbosity)
+                    .map(|(_, s)| s.as_str())
+                    .collect();
                 if capture.is_empty() {
                     None
                 } else {
-                    Some(capture.clone())
+                    Some(capture)
                 }
             }
         }
@@ -103,9 +401,13 @@ impl Mode {
             Self::Stdout {
                 ydp: _ydp,
                 new_line,
+                visualise,
             } => {
                 *_ydp = ydp;
                 *new_line = true;
+                if let Some(visualise) = visualise {
+                    visualise.reset();
+                }
             }
             Self::Capture {
                 ydp: _ydp,
@@ -129,10 +431,8 @@ impl Mode {
             }
         }
     }
-}
 
-impl Write for Mode {
-    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+    fn write_level(&mut self, level: Level, s: &str) {
         use std::io::Write;
         macro_rules! output {
             ($ydp:ident, $new_line:ident, $w:expr) => {{
@@ -163,16 +463,28 @@ impl Write for Mode {
 
         match self {
             Self::NoOutput => {}
-            Self::Stdout { ydp, new_line } => {
+            Self::Stdout { ydp, new_line, .. } => {
                 output!(ydp, new_line, std::io::stdout().lock())
             }
             Self::Capture {
                 ydp,
                 new_line,
                 capture,
-            } => output!(ydp, new_line, capture),
+            } => {
+                let mut buf = String::new();
+                output!(ydp, new_line, &mut buf);
+                match capture.last_mut() {
+                    Some((last, last_buf)) if *last == level => last_buf.push_str(&buf),
+                    _ => capture.push((level, buf)),
+                }
+            }
         }
+    }
+}
 
+impl Write for Mode {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.write_level(Level::Info, s);
         Ok(())
     }
 
@@ -184,13 +496,122 @@ impl Write for Mode {
 
         Ok(())
     }
+}
 
-    fn write_char(&mut self, c: char) -> std::fmt::Result {
-        match self {
-            Self::NoOutput => {}
-            Self::Stdout { .. } | Self::Capture { .. } => self.write_str(&format!("{c}"))?,
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn capture_levels() {
+        let mut output = Output::default();
+        output.capture();
+        output.start_run(YearDayPart::new(2024, 1, 2));
+        output.write_level(Level::Info, format_args!("info\n"));
+        output.write_level(Level::Debug, format_args!("debug "));
+        output.write_level(Level::Debug, format_args!("line\n"));
+        output.write_level(Level::Trace, format_args!("trace\n"));
+
+        assert_eq!(
+            output.get_capture(),
+            Some("2024-01 Part 2: info\n".to_string())
+        );
+        output.verbosity(Level::Debug);
+        assert_eq!(
+            output.get_capture(),
+            Some("2024-01 Part 2: info\n2024-01 Part 2: debug line\n".to_string())
+        );
+        output.verbosity(Level::Trace);
+        assert_eq!(
+            output
+                .mode
+                .get_capture(Level::Trace)
+                .unwrap()
+                .lines()
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "already locked by an `output` closure")]
+    fn output_is_not_reentrant() {
+        super::output(|_| super::output(|_| ()));
+    }
+
+    #[test]
+    fn threads_share_output() {
+        super::output(|output| {
+            output.capture();
+            output.start_run(YearDayPart::new(2024, 3, 1));
+        });
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                s.spawn(move || {
+                    for i in 0..100 {
+                        crate::print!("thread {t} ");
+                        crate::println!("line {i}");
+                    }
+                    // Unterminated lines are flushed with the run
+                    crate::print!("thread {t} done");
+                });
+            }
+        });
+
+        let capture = super::output(|output| {
+            let capture = output.get_capture().unwrap();
+            output.no_output();
+            capture
+        });
+        let lines: Vec<&str> = capture.lines().collect();
+        assert_eq!(lines.len(), 404);
+        for t in 0..4 {
+            let prefix = format!("2024-03 Part 1: thread {t} ");
+            let thread_lines: Vec<&str> = lines
+                .iter()
+                .filter_map(|l| l.strip_prefix(prefix.as_str()))
+                .collect();
+            let expected: Vec<String> = (0..100)
+                .map(|i| format!("line {i}"))
+                .chain(std::iter::once("done".to_string()))
+                .collect();
+            assert_eq!(thread_lines, expected);
         }
+    }
 
-        Ok(())
+    #[test]
+    fn partial_lines_stay_with_run() {
+        let output = Mutex::new(Output::new());
+        {
+            let mut output = output.lock().unwrap();
+            output.capture();
+            output.start_run(YearDayPart::new(2024, 1, 1));
+        }
+
+        // The worker is still running when the run's output is read
+        let written = std::sync::Barrier::new(2);
+        let checked = std::sync::Barrier::new(2);
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                output
+                    .lock()
+                    .unwrap()
+                    .write_pending(Level::Info, "part1 partial");
+                written.wait();
+                checked.wait();
+            });
+            written.wait();
+            let mut output = output.lock().unwrap();
+            output.write_pending(Level::Info, "main ");
+            assert_eq!(
+                output.get_capture(),
+                Some("2024-01 Part 1: part1 partial\n2024-01 Part 1: main \n".to_string())
+            );
+            output.start_run(YearDayPart::new(2024, 1, 2));
+            assert_eq!(output.get_capture(), None);
+            checked.wait();
+        });
     }
 }
@@ -1,24 +1,267 @@
-use crate::Error;
+use crate::{Error, ErrorContext, Integer, Numbers};
+use std::borrow::Cow;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
-use std::ops::{BitOr, Deref, DerefMut};
+use std::iter::{Copied, Zip};
+use std::ops::{BitOr, Deref, DerefMut, RangeFrom};
 use std::path::Path;
+use std::str::FromStr;
+
+type LineFn<T, E = Error> = fn(&str) -> Result<T, E>;
+
+pub trait FromLine: Sized {
+    fn from_line(line: &str) -> Result<Self, Error>;
+}
+
+// Column (1 based) of `sub` within `line`, `sub` must be a slice of `line`
+fn column_of(line: &str, sub: &str) -> usize {
+    sub.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+// All integers in `s`.  A '-' directly before a digit is a sign unless it follows a digit.
+pub fn numbers<T: Integer>(s: &str) -> Result<Vec<T>, Error> {
+    Numbers::new(s.as_bytes()).collect()
+}
+
+pub fn split_fields<T>(line: &str, sep: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    line.split(sep)
+        .map(|field| field.parse::<T>().at_column(column_of(line, field)))
+        .collect()
+}
+
+// Tuples of fields captured by `scan`
+pub trait Scan: Sized {
+    const FIELDS: usize;
+
+    fn from_fields(line: &str, fields: &[&str]) -> Result<Self, Error>;
+}
+
+macro_rules! impl_scan {
+    ($($t:ident),+) => {
+        impl<$($t),+> Scan for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Into<Error>,)+
+        {
+            const FIELDS: usize = [$(stringify!($t)),+].len();
+
+            fn from_fields(line: &str, fields: &[&str]) -> Result<Self, Error> {
+                let mut fields = fields.iter();
+                Ok(($({
+                    let field = fields.next().unwrap();
+                    field.parse::<$t>().at_column(column_of(line, field))?
+                },)+))
+            }
+        }
+    };
+}
+
+impl_scan!(A);
+impl_scan!(A, B);
+impl_scan!(A, B, C);
+impl_scan!(A, B, C, D);
+impl_scan!(A, B, C, D, E);
+impl_scan!(A, B, C, D, E, F);
+impl_scan!(A, B, C, D, E, F, G);
+impl_scan!(A, B, C, D, E, F, G, H);
+
+// Match `line` against `pattern`, where each `{}` captures the text up to the next literal part
+// of the pattern (or the end of the line) and is parsed in to the matching tuple field.
+pub fn scan<T: Scan>(line: &str, pattern: &str) -> Result<T, Error> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    if literals.len() - 1 != T::FIELDS {
+        return Err(Error::InvalidInput(format!(
+            "Pattern {pattern:?} has {} fields, expected {}",
+            literals.len() - 1,
+            T::FIELDS
+        )));
+    }
+
+    let expect = |rest: &str, literal: &str| -> Result<usize, Error> {
+        if rest.starts_with(literal) {
+            Ok(literal.len())
+        } else {
+            Err(Error::InvalidInput(format!("Expected {literal:?}")))
+                .at_column(column_of(line, rest))
+        }
+    };
+
+    let mut rest = &line[expect(line, literals[0])?..];
+    let mut fields = Vec::with_capacity(T::FIELDS);
+    for (idx, literal) in literals.iter().enumerate().skip(1) {
+        let end = if literal.is_empty() {
+            if idx != literals.len() - 1 {
+                return Err(Error::InvalidInput(format!(
+                    "Pattern {pattern:?} has adjacent fields"
+                )));
+            }
+            rest.len()
+        } else {
+            match rest.find(literal) {
+                Some(end) => end,
+                None => {
+                    return Err(Error::InvalidInput(format!("Expected {literal:?}")))
+                        .at_column(column_of(line, rest) + rest.len())
+                }
+            }
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end..];
+        rest = &rest[expect(rest, literal)?..];
+    }
+    if !rest.is_empty() {
+        return Err(Error::InvalidInput(format!("Unexpected {rest:?}")))
+            .at_column(column_of(line, rest));
+    }
+
+    T::from_fields(line, &fields)
+}
 
 #[derive(Debug)]
-pub struct Lines(Vec<String>);
+pub struct Lines {
+    lines: Vec<String>,
+    numbers: Vec<usize>,
+    sections: Option<Vec<usize>>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct LinesOpt(u8);
+pub struct LinesOpt {
+    flags: u8,
+    comments: &'static [&'static str],
+    tab_width: usize,
+}
 
 impl LinesOpt {
-    pub const RAW: Self = Self(0);
-    pub const TRIM: Self = Self(1 << 0);
-    pub const REMOVE_COMMENTS: Self = Self(1 << 1);
-    pub const REMOVE_EMPTY: Self = Self(2 << 1);
-    pub const ALL: Self = Self(!0);
+    const DEFAULT_COMMENTS: &'static [&'static str] = &["#"];
+
+    const fn flags(flags: u8) -> Self {
+        Self {
+            flags,
+            comments: Self::DEFAULT_COMMENTS,
+            tab_width: 0,
+        }
+    }
+
+    pub const RAW: Self = Self::flags(0);
+    pub const TRIM_START: Self = Self::flags(1 << 0);
+    pub const TRIM_END: Self = Self::flags(1 << 1);
+    pub const TRIM: Self = Self::flags(Self::TRIM_START.flags | Self::TRIM_END.flags);
+    pub const REMOVE_COMMENTS: Self = Self::flags(1 << 2);
+    pub const REMOVE_EMPTY: Self = Self::flags(1 << 3);
+    pub const SECTIONS: Self = Self::flags(1 << 4);
+    pub const CRLF: Self = Self::flags(1 << 5);
+    pub const ALL: Self = Self::flags((1 << 6) - 1);
+
+    pub const fn new() -> Self {
+        Self::RAW
+    }
+
+    const fn with(mut self, rhs: Self) -> Self {
+        self.flags |= rhs.flags;
+        self
+    }
+
+    pub const fn trim(self) -> Self {
+        self.with(Self::TRIM)
+    }
+
+    pub const fn trim_start(self) -> Self {
+        self.with(Self::TRIM_START)
+    }
+
+    pub const fn trim_end(self) -> Self {
+        self.with(Self::TRIM_END)
+    }
+
+    pub const fn remove_comments(self) -> Self {
+        self.with(Self::REMOVE_COMMENTS)
+    }
+
+    // Lines starting with any of `markers` (after trimming) are comments, instead of '#'
+    pub const fn comments(mut self, markers: &'static [&'static str]) -> Self {
+        self.comments = markers;
+        self.with(Self::REMOVE_COMMENTS)
+    }
+
+    pub const fn remove_empty(self) -> Self {
+        self.with(Self::REMOVE_EMPTY)
+    }
+
+    pub const fn sections(self) -> Self {
+        self.with(Self::SECTIONS)
+    }
+
+    // Strip every trailing '\r', not just the one before '\n'
+    pub const fn crlf(self) -> Self {
+        self.with(Self::CRLF)
+    }
+
+    // Replace tabs with spaces up to the next multiple of `width`, 0 leaves tabs alone
+    pub const fn expand_tabs(mut self, width: usize) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    pub(crate) fn contains(&self, rhs: Self) -> bool {
+        self.flags & rhs.flags == rhs.flags
+    }
+
+    pub(crate) fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    // Line endings, tabs, trimming and comments.  `None` if the line is a comment.
+    fn clean<'a>(&self, line: &'a str) -> Option<Cow<'a, str>> {
+        let mut line = Cow::Borrowed(line);
+        if self.contains(Self::CRLF) {
+            line = strip_cow(line, |l| l.trim_end_matches('\r'));
+        }
+        if self.tab_width != 0 && line.contains('\t') {
+            line = Cow::Owned(expand_tabs(&line, self.tab_width));
+        }
+        if self.contains(Self::TRIM_START) {
+            line = strip_cow(line, str::trim_start);
+        }
+        if self.contains(Self::TRIM_END) {
+            line = strip_cow(line, str::trim_end);
+        }
+        if self.contains(Self::REMOVE_COMMENTS) && self.is_comment(line.as_bytes()) {
+            return None;
+        }
+        Some(line)
+    }
+
+    pub(crate) fn clean_bytes<'a>(&self, mut line: &'a [u8]) -> Option<&'a [u8]> {
+        if self.contains(Self::CRLF) {
+            while let Some(l) = line.strip_suffix(b"\r") {
+                line = l;
+            }
+        }
+        if self.contains(Self::TRIM_START) {
+            line = trim_start_bytes(line);
+        }
+        if self.contains(Self::TRIM_END) {
+            line = trim_end_bytes(line);
+        }
+        if self.contains(Self::REMOVE_COMMENTS) && self.is_comment(line) {
+            return None;
+        }
+        Some(line)
+    }
 
-    fn contains(&self, rhs: Self) -> bool {
-        self.0 & rhs.0 == rhs.0
+    fn is_comment(&self, line: &[u8]) -> bool {
+        self.comments
+            .iter()
+            .any(|marker| line.starts_with(marker.as_bytes()))
+    }
+}
+
+impl Default for LinesOpt {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -26,34 +269,106 @@ impl BitOr for LinesOpt {
     type Output = Self;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        Self(self.0 | rhs.0)
+        Self {
+            flags: self.flags | rhs.flags,
+            comments: if rhs.comments == Self::DEFAULT_COMMENTS {
+                self.comments
+            } else {
+                rhs.comments
+            },
+            tab_width: if rhs.tab_width == 0 {
+                self.tab_width
+            } else {
+                rhs.tab_width
+            },
+        }
+    }
+}
+
+// `str::trim_start` on the UTF-8 at the start of `line`
+pub(crate) fn trim_start_bytes(line: &[u8]) -> &[u8] {
+    let valid = line.utf8_chunks().next().map_or("", |chunk| chunk.valid());
+    &line[valid.len() - valid.trim_start().len()..]
+}
+
+// `str::trim_end` on the UTF-8 at the end of `line`
+pub(crate) fn trim_end_bytes(line: &[u8]) -> &[u8] {
+    let valid = line
+        .utf8_chunks()
+        .last()
+        .filter(|chunk| chunk.invalid().is_empty())
+        .map_or("", |chunk| chunk.valid());
+    &line[..line.len() - (valid.len() - valid.trim_end().len())]
+}
+
+fn strip_cow<'a>(line: Cow<'a, str>, f: impl Fn(&str) -> &str) -> Cow<'a, str> {
+    match line {
+        Cow::Borrowed(l) => Cow::Borrowed(f(l)),
+        Cow::Owned(l) => Cow::Owned(f(&l).to_string()),
     }
 }
 
+fn expand_tabs(line: &str, width: usize) -> String {
+    let mut expanded = String::with_capacity(line.len());
+    let mut column = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = width - column % width;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+    expanded
+}
+
 impl Lines {
     pub fn from_bufread(r: impl BufRead, options: LinesOpt) -> Result<Self, Error> {
         let mut lines = Vec::new();
-        for line in r.lines() {
+        let mut numbers = Vec::new();
+        let mut sections = if options.contains(LinesOpt::SECTIONS) {
+            Some(vec![0])
+        } else {
+            None
+        };
+        for (number, line) in r.lines().enumerate() {
             let line = line?;
-            let mut l = line.as_str();
-            if options.contains(LinesOpt::TRIM) {
-                l = l.trim();
-            }
-            if options.contains(LinesOpt::REMOVE_COMMENTS) && l.starts_with('#') {
+            let Some(l) = options.clean(&line) else {
                 continue;
+            };
+            if let Some(sections) = &mut sections {
+                if l.trim().is_empty() {
+                    // Runs of blank lines only start one new section
+                    if *sections.last().unwrap() != lines.len() {
+                        sections.push(lines.len());
+                    }
+                    continue;
+                }
             }
             if options.contains(LinesOpt::REMOVE_EMPTY) && l.is_empty() {
                 continue;
             }
-            lines.push(String::from(l));
+            lines.push(l.into_owned());
+            numbers.push(number + 1);
+        }
+        if let Some(sections) = &mut sections {
+            if sections.len() > 1 && *sections.last().unwrap() == lines.len() {
+                sections.pop();
+            }
         }
 
-        Ok(Self(lines))
+        Ok(Self {
+            lines,
+            numbers,
+            sections,
+        })
     }
 
     pub fn single_line(mut self) -> Result<String, Error> {
         if self.len() == 1 {
-            Ok(self.remove(0))
+            Ok(self.lines.remove(0))
         } else {
             Err(Error::InvalidInput(format!(
                 "Expected only 1 line, got {}",
@@ -70,34 +385,310 @@ impl Lines {
         Self::from_reader(File::open(path)?, options)
     }
 
-    pub fn iter(&self) -> LinesIter {
-        LinesIter(self.0.iter())
+    pub fn iter(&self) -> LinesIter<'_> {
+        LinesIter {
+            iter: self.lines.iter(),
+            numbers: &self.numbers,
+            idx: 0,
+        }
+    }
+
+    // Sections are split at blank lines, or where they were removed when read with
+    // `LinesOpt::SECTIONS`
+    pub fn sections(&self) -> impl Iterator<Item = Section<'_>> {
+        let numbers = if self.numbers.len() == self.lines.len() {
+            self.numbers.as_slice()
+        } else {
+            &[]
+        };
+        let ranges: Vec<(usize, usize)> = match &self.sections {
+            Some(starts) if numbers.len() == self.lines.len() => starts
+                .iter()
+                .zip(
+                    starts
+                        .iter()
+                        .skip(1)
+                        .chain(std::iter::once(&self.lines.len())),
+                )
+                .map(|(s, e)| (*s, *e))
+                .collect(),
+            _ => {
+                let mut ranges = Vec::new();
+                let mut start = 0;
+                for (idx, line) in self.lines.iter().enumerate() {
+                    if line.trim().is_empty() {
+                        if start != idx {
+                            ranges.push((start, idx));
+                        }
+                        start = idx + 1;
+                    }
+                }
+                if start != self.lines.len() {
+                    ranges.push((start, self.lines.len()));
+                }
+                ranges
+            }
+        };
+
+        ranges.into_iter().map(move |(s, e)| Section {
+            lines: &self.lines[s..e],
+            numbers: numbers.get(s..e).unwrap_or(&[]),
+        })
+    }
+
+    // Line number in the original input, falls back to the index if lines have been modified
+    pub fn line_number(&self, idx: usize) -> usize {
+        if self.numbers.len() == self.lines.len() {
+            self.numbers[idx]
+        } else {
+            idx + 1
+        }
     }
 }
 
+// Parsing helpers shared by `Lines` and `Section`, built on `iter()`
+macro_rules! impl_parse {
+    ($ty:ty $(, $lt:lifetime)?) => {
+        impl$(<$lt>)? $ty {
+            pub fn try_map<'a, T, E, F>(&'a self, f: F) -> Result<Vec<T>, Error>
+            where
+                E: Into<Error>,
+                F: FnMut(&'a str) -> Result<T, E>,
+            {
+                self.iter().try_map(f).collect()
+            }
+
+            pub fn parse_each<T>(&self) -> Result<Vec<T>, Error>
+            where
+                T: FromStr,
+                T::Err: Into<Error>,
+            {
+                self.iter().parse_each().collect()
+            }
+
+            pub fn numbers<T: Integer>(&self) -> Result<Vec<Vec<T>>, Error> {
+                self.iter().numbers().collect()
+            }
+
+            pub fn split_fields<T>(&self, sep: &str) -> Result<Vec<Vec<T>>, Error>
+            where
+                T: FromStr,
+                T::Err: Into<Error>,
+            {
+                self.iter().split_fields(sep).collect()
+            }
+
+            pub fn parse<T: FromLine>(&self) -> Result<Vec<T>, Error> {
+                self.iter().parse().collect()
+            }
+
+            pub fn scan_lines<T: Scan>(&self, pattern: &str) -> Result<Vec<T>, Error> {
+                self.iter().scan_lines(pattern).collect()
+            }
+        }
+    };
+}
+
+impl_parse!(Lines);
+impl_parse!(Section<'s>, 's);
+
 impl Deref for Lines {
     type Target = Vec<String>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.lines
     }
 }
 
 impl DerefMut for Lines {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.lines
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Section<'a> {
+    lines: &'a [String],
+    numbers: &'a [usize],
+}
+
+impl<'a> Section<'a> {
+    pub fn iter(&self) -> LinesIter<'a> {
+        LinesIter {
+            iter: self.lines.iter(),
+            numbers: self.numbers,
+            idx: 0,
+        }
+    }
+
+    pub fn single_line(&self) -> Result<&'a str, Error> {
+        if self.lines.len() == 1 {
+            Ok(self.lines[0].as_str())
+        } else {
+            Err(Error::InvalidInput(format!(
+                "Expected only 1 line, got {}",
+                self.lines.len()
+            )))
+        }
     }
 }
 
-pub struct LinesIter<'a>(std::slice::Iter<'a, String>);
+impl Deref for Section<'_> {
+    type Target = [String];
+
+    fn deref(&self) -> &Self::Target {
+        self.lines
+    }
+}
+
+pub struct LinesIter<'a> {
+    iter: std::slice::Iter<'a, String>,
+    numbers: &'a [usize],
+    idx: usize,
+}
+
+impl<'a> LinesIter<'a> {
+    // Line number of the most recently returned line, 0 before the first
+    pub fn line_number(&self) -> usize {
+        if self.idx == 0 {
+            0
+        } else if self.numbers.len() == self.idx + self.iter.len() {
+            self.numbers[self.idx - 1]
+        } else {
+            self.idx
+        }
+    }
+
+    pub fn numbered(self) -> Numbered<'a> {
+        Numbered(self)
+    }
+
+    pub fn try_map<T, E, F>(self, f: F) -> TryMap<'a, F>
+    where
+        E: Into<Error>,
+        F: FnMut(&'a str) -> Result<T, E>,
+    {
+        TryMap { iter: self, f }
+    }
+
+    pub fn parse_each<T>(self) -> TryMap<'a, LineFn<T, T::Err>>
+    where
+        T: FromStr,
+        T::Err: Into<Error>,
+    {
+        self.try_map(str::parse::<T>)
+    }
+
+    pub fn numbers<T: Integer>(self) -> TryMap<'a, LineFn<Vec<T>>> {
+        self.try_map(numbers::<T>)
+    }
+
+    pub fn split_fields<'s, T>(
+        self,
+        sep: &'s str,
+    ) -> TryMap<'a, impl FnMut(&'a str) -> Result<Vec<T>, Error> + 's>
+    where
+        T: FromStr,
+        T::Err: Into<Error>,
+    {
+        self.try_map(move |line| split_fields(line, sep))
+    }
+
+    pub fn parse<T: FromLine>(self) -> TryMap<'a, LineFn<T>> {
+        self.try_map(T::from_line)
+    }
+
+    // Not `scan`, which would hide `Iterator::scan`
+    pub fn scan_lines<'p, T: Scan>(
+        self,
+        pattern: &'p str,
+    ) -> TryMap<'a, impl FnMut(&'a str) -> Result<T, Error> + 'p> {
+        self.try_map(move |line| scan(line, pattern))
+    }
+}
 
 impl<'a> Iterator for LinesIter<'a> {
     type Item = &'a str;
     fn next(&mut self) -> Option<Self::Item> {
-        match self.0.next() {
-            Some(s) => Some(s.as_str()),
-            None => None,
-        }
+        let s = self.iter.next()?;
+        self.idx += 1;
+        Some(s.as_str())
+    }
+}
+
+pub struct Numbered<'a>(LinesIter<'a>);
+
+impl<'a> Iterator for Numbered<'a> {
+    type Item = (usize, &'a str);
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.0.next()?;
+        Some((self.0.line_number(), line))
+    }
+}
+
+// Lines paired with their line number in the input, so errors point at the right line even when
+// `LinesOpt` has removed some.  Plain strings are numbered from 1.
+pub trait NumberedLines<'a> {
+    type Iter: Iterator<Item = (usize, &'a str)>;
+    fn numbered_lines(self) -> Self::Iter;
+}
+
+impl<'a> NumberedLines<'a> for LinesIter<'a> {
+    type Iter = Numbered<'a>;
+    fn numbered_lines(self) -> Self::Iter {
+        self.numbered()
+    }
+}
+
+impl<'a> NumberedLines<'a> for Section<'a> {
+    type Iter = Numbered<'a>;
+    fn numbered_lines(self) -> Self::Iter {
+        self.iter().numbered()
+    }
+}
+
+impl<'a> NumberedLines<'a> for &'a Lines {
+    type Iter = Numbered<'a>;
+    fn numbered_lines(self) -> Self::Iter {
+        self.iter().numbered()
+    }
+}
+
+impl<'a, const N: usize> NumberedLines<'a> for [&'a str; N] {
+    type Iter = Zip<RangeFrom<usize>, std::array::IntoIter<&'a str, N>>;
+    fn numbered_lines(self) -> Self::Iter {
+        (1..).zip(self)
+    }
+}
+
+impl<'a, 'b> NumberedLines<'a> for &'b [&'a str] {
+    type Iter = Zip<RangeFrom<usize>, Copied<std::slice::Iter<'b, &'a str>>>;
+    fn numbered_lines(self) -> Self::Iter {
+        (1..).zip(self.iter().copied())
+    }
+}
+
+impl<'a> NumberedLines<'a> for Vec<&'a str> {
+    type Iter = Zip<RangeFrom<usize>, std::vec::IntoIter<&'a str>>;
+    fn numbered_lines(self) -> Self::Iter {
+        (1..).zip(self)
+    }
+}
+
+pub struct TryMap<'a, F> {
+    iter: LinesIter<'a>,
+    f: F,
+}
+
+impl<'a, T, E, F> Iterator for TryMap<'a, F>
+where
+    E: Into<Error>,
+    F: FnMut(&'a str) -> Result<T, E>,
+{
+    type Item = Result<T, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.iter.next()?;
+        Some((self.f)(line).at_line(self.iter.line_number(), line))
     }
 }
 
@@ -201,6 +792,246 @@ Yup  ";
         Ok(())
     }
 
+    #[test]
+    fn test_line_context() -> Result<(), Error> {
+        let lines = Lines::from_reader(Cursor::new("1\n\n2\nx3\n4"), LinesOpt::REMOVE_EMPTY)?;
+        assert_eq!(
+            lines.iter().numbered().collect::<Vec<_>>(),
+            [(1, "1"), (3, "2"), (4, "x3"), (5, "4")]
+        );
+
+        let err = lines.try_map(|l| l.parse::<usize>()).unwrap_err();
+        let pos = err.position().expect("Error should have a position");
+        assert_eq!(pos.line, Some(4));
+        assert_eq!(pos.column, None);
+        assert_eq!(pos.text.as_deref(), Some("x3"));
+
+        let err = lines
+            .try_map(|l| {
+                if l.starts_with('x') {
+                    Err(Error::InvalidInput("x".into())).at_column(1)
+                } else {
+                    Ok(l)
+                }
+            })
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 4, column 1: Invalid input: x");
+
+        let mut iter = lines.iter();
+        assert_eq!(iter.line_number(), 0);
+        iter.next();
+        assert_eq!(iter.line_number(), 1);
+        let empty = Lines::from_reader(Cursor::new(""), LinesOpt::RAW)?;
+        assert_eq!(empty.iter().line_number(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_parsing() -> Result<(), Error> {
+        let lines = Lines::from_reader(
+            Cursor::new("Sensor at x=2, y=-18: beacon at x=-2, y=15\n1-3 a: abc"),
+            LinesOpt::RAW,
+        )?;
+        assert_eq!(
+            lines.numbers::<isize>()?,
+            [vec![2, -18, -2, 15], vec![1, 3]]
+        );
+
+        let err = lines.numbers::<usize>().unwrap_err();
+        assert_eq!(
+            err.position(),
+            Some(&crate::InputPosition {
+                line: Some(1),
+                column: Some(18),
+                text: Some("Sensor at x=2, y=-18: beacon at x=-2, y=15".into()),
+            })
+        );
+
+        let lines = Lines::from_reader(Cursor::new("1,2,3\n4,x,6"), LinesOpt::RAW)?;
+        assert_eq!(
+            lines.iter().split_fields::<u8>(",").next().unwrap()?,
+            [1, 2, 3]
+        );
+        let err = lines.split_fields::<u8>(",").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: Could not parse integer: invalid digit found in string"
+        );
+
+        let lines = Lines::from_reader(Cursor::new("10\n-20\n"), LinesOpt::RAW)?;
+        assert_eq!(lines.parse_each::<i32>()?, [10, -20]);
+        assert!(lines.parse_each::<u32>().is_err());
+
+        #[derive(Debug, PartialEq)]
+        struct Move(char, usize);
+        impl FromLine for Move {
+            fn from_line(line: &str) -> Result<Self, Error> {
+                let (dir, dist) = line
+                    .split_once(' ')
+                    .ok_or_else(|| Error::InvalidInput(line.into()))?;
+                Ok(Self(dir.chars().next().unwrap(), dist.parse()?))
+            }
+        }
+        let lines = Lines::from_reader(Cursor::new("R 4\nU 10"), LinesOpt::RAW)?;
+        assert_eq!(lines.parse::<Move>()?, [Move('R', 4), Move('U', 10)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sections() -> Result<(), Error> {
+        const INPUT: &str = "47|53
+  # rule comment
+97|13
+
+ 
+75,47,61
+# update comment
+97,61,53
+
+";
+
+        let lines = Lines::from_reader(Cursor::new(INPUT), LinesOpt::TRIM)?;
+        let sections: Vec<Vec<&str>> = lines.sections().map(|s| s.iter().collect()).collect();
+        assert_eq!(
+            sections,
+            [
+                vec!["47|53", "# rule comment", "97|13"],
+                vec!["75,47,61", "# update comment", "97,61,53"]
+            ]
+        );
+
+        for options in [
+            LinesOpt::SECTIONS | LinesOpt::TRIM | LinesOpt::REMOVE_COMMENTS,
+            LinesOpt::ALL,
+        ] {
+            let lines = Lines::from_reader(Cursor::new(INPUT), options)?;
+            assert_eq!(lines.len(), 4);
+            let sections: Vec<_> = lines.sections().collect();
+            assert_eq!(sections.len(), 2);
+            assert_eq!(sections[0].split_fields::<u8>("|")?, [[47, 53], [97, 13]]);
+            assert_eq!(
+                sections[1].numbers::<u8>()?,
+                [vec![75, 47, 61], vec![97, 61, 53]]
+            );
+
+            // Line numbers still refer to the original input
+            let err = sections[1].split_fields::<u8>("|").unwrap_err();
+            assert_eq!(err.position().unwrap().line, Some(6));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan() -> Result<(), Error> {
+        let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let (sx, sy, bx, by): (isize, isize, isize, isize) = crate::scan!(
+            line,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}"
+        )?;
+        assert_eq!((sx, sy, bx, by), (2, 18, -2, 15));
+
+        let claim = crate::scan!("#1 @ 1,3: 4x4", "#{} @ {},{}: {}x{}", u32, u8, u8, u8, u8)?;
+        assert_eq!(claim, (1, 1, 3, 4, 4));
+
+        let (name, rest) = scan::<(String, String)>("a -> b, c", "{} -> {}")?;
+        assert_eq!((name.as_str(), rest.as_str()), ("a", "b, c"));
+
+        let err = scan::<(u8, u8)>("x=1; y=2", "x={}, y={}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "column 9: Invalid input: Expected \", y=\""
+        );
+        let err = scan::<(u8,)>("x=1!", "x={}").unwrap_err();
+        assert_eq!(err.position().unwrap().column, Some(3));
+        let err = scan::<(u8,)>("y=1", "x={}").unwrap_err();
+        assert_eq!(err.position().unwrap().column, Some(1));
+        assert!(scan::<(u8,)>("x=1, y=2", "x={}, y=").is_err());
+        assert!(scan::<(u8, u8)>("x=1", "x={}").is_err());
+
+        let lines = Lines::from_reader(Cursor::new("p=0,4\np=3,x"), LinesOpt::RAW)?;
+        let mut iter = lines.iter().scan_lines::<(u8, u8)>("p={},{}");
+        assert_eq!(iter.next().unwrap()?, (0, 4));
+        let err = iter.next().unwrap().unwrap_err();
+        assert_eq!(
+            err.position(),
+            Some(&crate::InputPosition {
+                line: Some(2),
+                column: Some(5),
+                text: Some("p=3,x".into()),
+            })
+        );
+
+        let lengths: Vec<usize> = lines
+            .iter()
+            .scan(0, |total, line| {
+                *total += line.len();
+                Some(*total)
+            })
+            .collect();
+        assert_eq!(lengths, [5, 10]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder() -> Result<(), Error> {
+        assert_eq!(LinesOpt::new(), LinesOpt::RAW);
+        assert_eq!(
+            LinesOpt::new().trim().remove_empty(),
+            LinesOpt::TRIM | LinesOpt::REMOVE_EMPTY
+        );
+        assert_eq!(LinesOpt::TRIM_START | LinesOpt::TRIM_END, LinesOpt::TRIM);
+        assert!(!LinesOpt::TRIM.contains(LinesOpt::REMOVE_COMMENTS));
+        assert!(!LinesOpt::REMOVE_EMPTY.contains(LinesOpt::REMOVE_COMMENTS));
+
+        const INPUT: &str = "  a  \n\tb\n// c\n  ; d\ne\r\r\n\n";
+        let read = |options| -> Result<Vec<String>, Error> {
+            Ok(Lines::from_reader(Cursor::new(INPUT), options)?.to_vec())
+        };
+        assert_eq!(
+            read(LinesOpt::new().trim_start())?,
+            ["a  ", "b", "// c", "; d", "e\r", ""]
+        );
+        assert_eq!(
+            read(LinesOpt::new().trim_end())?,
+            ["  a", "\tb", "// c", "  ; d", "e", ""]
+        );
+        assert_eq!(
+            read(LinesOpt::new().crlf().remove_empty())?,
+            ["  a  ", "\tb", "// c", "  ; d", "e"]
+        );
+        assert_eq!(
+            read(LinesOpt::new().trim().comments(&["//", ";"]).remove_empty())?,
+            ["a", "b", "e"]
+        );
+        // The default '#' marker no longer applies
+        assert_eq!(
+            Lines::from_reader(Cursor::new("#a\n//b"), LinesOpt::new().comments(&["//"]))?.to_vec(),
+            ["#a"]
+        );
+        assert_eq!(read(LinesOpt::new().expand_tabs(4).trim_end())?[1], "    b");
+        assert_eq!(
+            Lines::from_reader(Cursor::new("ab\tc\t\td"), LinesOpt::new().expand_tabs(4))?
+                .single_line()?,
+            "ab  c       d"
+        );
+
+        // Borrowed lines share the same options
+        let options = LinesOpt::new().trim().comments(&["//", ";"]).crlf();
+        let byte_lines: Vec<&[u8]> = crate::ByteLines::new(INPUT.as_bytes(), options)?.collect();
+        assert_eq!(
+            byte_lines,
+            read(options)?
+                .iter()
+                .map(|l| l.as_bytes())
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_all() -> Result<(), Error> {
         assert_eq!(
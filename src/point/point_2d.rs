@@ -98,6 +98,11 @@ impl<T: Integer, const INVERT_SORT: bool, const REVERSE_SORT: bool>
         ]
     }
 
+    // Wrap in to 0..bounds.x, 0..bounds.y
+    pub fn rem_euclid(&self, bounds: Self) -> Self {
+        Self::new(self.x.rem_euclid(bounds.x), self.y.rem_euclid(bounds.y))
+    }
+
     pub fn scale(&self, v: T) -> Self {
         Self {
             x: self.x * v,
@@ -214,4 +219,15 @@ mod test {
                 assert_eq!(n, d);
             });
     }
+
+    #[test]
+    fn test_rem_euclid() {
+        let bounds = Point2D::<isize>::new(11, 7);
+        assert_eq!(Point2D::new(-1, 7).rem_euclid(bounds), Point2D::new(10, 0));
+        assert_eq!(Point2D::new(25, -15).rem_euclid(bounds), Point2D::new(3, 6));
+        assert_eq!(
+            Point2D::<u8>::new(12, 6).rem_euclid(Point2D::new(11, 7)),
+            Point2D::new(1, 6)
+        );
+    }
 }
@@ -0,0 +1,93 @@
+use crate::{HashSet, Point2D};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    // `Point2D::cardinal_neighbors`
+    Four,
+    // `Point2D::all_neighbors`
+    Eight,
+}
+
+impl Connectivity {
+    pub fn neighbors(self, p: Point2D<isize>) -> impl Iterator<Item = Point2D<isize>> {
+        let (cardinal, all) = match self {
+            Self::Four => (Some(p.cardinal_neighbors()), None),
+            Self::Eight => (None, Some(p.all_neighbors())),
+        };
+        cardinal
+            .into_iter()
+            .flatten()
+            .chain(all.into_iter().flatten())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Point2D<isize>>,
+}
+
+impl Region {
+    // Iterative, `claim` returns true if a cell belongs to the region and hasn't been claimed yet
+    pub(crate) fn flood_fill<N, I, F>(start: Point2D<isize>, mut neighbors: N, mut claim: F) -> Self
+    where
+        N: FnMut(Point2D<isize>) -> I,
+        I: Iterator<Item = Point2D<isize>>,
+        F: FnMut(Point2D<isize>) -> bool,
+    {
+        let mut cells = HashSet::default();
+        if !claim(start) {
+            return Self { cells };
+        }
+        cells.insert(start);
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            for n in neighbors(p) {
+                if claim(n) {
+                    cells.insert(n);
+                    stack.push(n);
+                }
+            }
+        }
+        Self { cells }
+    }
+
+    pub fn cells(&self) -> &HashSet<Point2D<isize>> {
+        &self.cells
+    }
+
+    pub fn contains(&self, p: Point2D<isize>) -> bool {
+        self.cells.contains(&p)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // Edges of cells that don't touch another cell in the region, including around holes.  Regions
+    // that wrap around a grid have edges where they wrap.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|p| p.cardinal_neighbors())
+            .filter(|n| !self.contains(*n))
+            .count()
+    }
+
+    // Straight runs of perimeter, counted as the number of corners
+    pub fn sides(&self) -> usize {
+        const DIRS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+        let mut corners = 0;
+        for p in self.cells.iter() {
+            for (i, a) in DIRS.iter().enumerate() {
+                let b = DIRS[(i + 1) % 4];
+                let in_a = self.contains(Point2D::new(p.x + a.0, p.y + a.1));
+                let in_b = self.contains(Point2D::new(p.x + b.0, p.y + b.1));
+                let in_diagonal = self.contains(Point2D::new(p.x + a.0 + b.0, p.y + a.1 + b.1));
+                if (!in_a && !in_b) || (in_a && in_b && !in_diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+}
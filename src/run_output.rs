@@ -1,7 +1,14 @@
+use crate::{Integer, Letters, Point2D, Point3D};
 use std::fmt::Display;
 
 pub enum RunOutput {
     String(String),
+    Letters(Letters),
+    List(Vec<RunOutput>),
+    Float(f64, Option<usize>),
+    Point2D(Point2D<isize>),
+    Point3D(Point3D<isize>),
+    Bool(bool),
     Usize(usize),
     Isize(isize),
     U128(u128),
@@ -12,6 +19,21 @@ impl Display for RunOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::String(s) => write!(f, "{s}"),
+            Self::Letters(l) => write!(f, "{l}"),
+            Self::List(list) => {
+                for (idx, v) in list.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                Ok(())
+            }
+            Self::Float(v, Some(precision)) => write!(f, "{v:.precision$}"),
+            Self::Float(v, None) => write!(f, "{v}"),
+            Self::Point2D(p) => write!(f, "{p}"),
+            Self::Point3D(p) => write!(f, "{p}"),
+            Self::Bool(v) => write!(f, "{v}"),
             Self::Usize(v) => write!(f, "{v}"),
             Self::Isize(v) => write!(f, "{v}"),
             Self::U128(v) => write!(f, "{v}"),
@@ -20,6 +42,99 @@ impl Display for RunOutput {
     }
 }
 
+impl RunOutput {
+    pub fn float(v: f64, precision: usize) -> Self {
+        Self::Float(v, Some(precision))
+    }
+
+    // The value compared against the expect file
+    pub fn answer(&self) -> String {
+        match self {
+            Self::Letters(l) => l.text().into(),
+            _ => self.to_string(),
+        }
+    }
+
+    // Ignores CRLF and trailing whitespace.  With `numeric`, comma separated numbers are
+    // compared by value so 0042 matches 42.
+    pub fn matches(&self, expect: &str, numeric: bool) -> bool {
+        fn normalise(s: &str) -> String {
+            s.lines()
+                .map(|l| l.trim_end())
+                .collect::<Vec<&str>>()
+                .join("\n")
+                .trim_end()
+                .to_string()
+        }
+
+        let answer = normalise(&self.answer());
+        let expect = normalise(expect);
+        if answer == expect {
+            return true;
+        }
+        if !numeric {
+            return false;
+        }
+
+        let answer: Vec<&str> = answer.split(',').map(|s| s.trim()).collect();
+        let expect: Vec<&str> = expect.split(',').map(|s| s.trim()).collect();
+        answer.len() == expect.len()
+            && answer.iter().zip(expect.iter()).all(|(a, e)| {
+                if a == e {
+                    true
+                } else if let (Ok(a), Ok(e)) = (a.parse::<i128>(), e.parse::<i128>()) {
+                    a == e
+                } else if let (Ok(a), Ok(e)) = (a.parse::<f64>(), e.parse::<f64>()) {
+                    a == e
+                } else {
+                    false
+                }
+            })
+    }
+}
+
+impl From<&str> for RunOutput {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl<T: Into<RunOutput>> From<Vec<T>> for RunOutput {
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(|v| v.into()).collect())
+    }
+}
+
+impl<T: Integer, const INVERT_SORT: bool, const REVERSE_SORT: bool>
+    From<Point2D<T, INVERT_SORT, REVERSE_SORT>> for RunOutput
+{
+    fn from(value: Point2D<T, INVERT_SORT, REVERSE_SORT>) -> Self {
+        Self::Point2D(Point2D::new(value.x.as_isize(), value.y.as_isize()))
+    }
+}
+
+impl<T: Integer> From<Point3D<T>> for RunOutput {
+    fn from(value: Point3D<T>) -> Self {
+        Self::Point3D(Point3D::new(
+            value.x.as_isize(),
+            value.y.as_isize(),
+            value.z.as_isize(),
+        ))
+    }
+}
+
+impl From<f32> for RunOutput {
+    fn from(value: f32) -> Self {
+        Self::Float(value as f64, None)
+    }
+}
+
+impl From<f64> for RunOutput {
+    fn from(value: f64) -> Self {
+        Self::Float(value, None)
+    }
+}
+
 macro_rules! impl_from {
     ($ty:ty, $cast:ty, $enum:ident) => {
         impl From<$ty> for RunOutput {
@@ -31,6 +146,8 @@ macro_rules! impl_from {
 }
 
 impl_from!(String, String, String);
+impl_from!(Letters, Letters, Letters);
+impl_from!(bool, bool, Bool);
 impl_from!(u8, usize, Usize);
 impl_from!(u16, usize, Usize);
 impl_from!(u32, usize, Usize);
@@ -43,3 +160,44 @@ impl_from!(i32, isize, Isize);
 impl_from!(i64, isize, Isize);
 impl_from!(isize, isize, Isize);
 impl_from!(i128, i128, I128);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(RunOutput::from(vec![1, 2, 3]).to_string(), "1,2,3");
+        assert_eq!(RunOutput::from(vec!["a", "b"]).to_string(), "a,b");
+        assert_eq!(RunOutput::float(1.0 / 3.0, 3).to_string(), "0.333");
+        assert_eq!(RunOutput::from(2.5).to_string(), "2.5");
+        assert_eq!(RunOutput::from(Point2D::<u8>::new(3, 4)).to_string(), "3,4");
+        assert_eq!(
+            RunOutput::from(Point3D::new(-1, 0, 1)).to_string(),
+            "-1,0,1"
+        );
+        assert_eq!(RunOutput::from(true).to_string(), "true");
+    }
+
+    #[test]
+    fn matches() {
+        let output = RunOutput::from(42);
+        assert!(output.matches("42", false));
+        assert!(output.matches("42 \r\n", false));
+        assert!(output.matches("42\n\n", false));
+        assert!(!output.matches("0042", false));
+        assert!(output.matches("0042", true));
+        assert!(!output.matches("43", true));
+
+        let output = RunOutput::from("a  \nb");
+        assert!(output.matches("a\r\nb\r\n", false));
+
+        let output = RunOutput::from(vec![1, 20]);
+        assert!(output.matches("01, 20", true));
+        assert!(!output.matches("1,20,3", true));
+
+        let output = RunOutput::float(0.5, 2);
+        assert!(!output.matches("0.5", false));
+        assert!(output.matches("0.5", true));
+    }
+}
@@ -1,36 +1,50 @@
-use super::{Error, InputFileCache};
+use super::{Error, InputFileCache, Level, SearchType, Visualise};
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 mod args;
+mod calendar;
 mod run;
 mod times;
 
+pub use calendar::{AdventOfCode, Calendar, Custom, EverybodyCodes};
 use times::TimesCacheEntry;
 
-pub struct Config<RegisterFunc, MostRecentDayFunc>
+pub struct Config<RegisterFunc, C = AdventOfCode>
 where
     RegisterFunc: Fn(&mut BTreeMap<(usize, usize), (u8, super::NewRunner)>),
-    MostRecentDayFunc: FnOnce(usize, usize, usize) -> (usize, usize),
+    C: Calendar,
 {
     download_input: bool,
     allow_copy: bool,
+    numeric_compare: bool,
     readme_header: &'static str,
     register_func: RegisterFunc,
-    most_recent_day_func: MostRecentDayFunc,
+    calendar: C,
 }
 
-impl<RegisterFunc, MostRecentDayFunc> Config<RegisterFunc, MostRecentDayFunc>
+impl<RegisterFunc> Config<RegisterFunc, AdventOfCode>
 where
     RegisterFunc: Fn(&mut BTreeMap<(usize, usize), (u8, super::NewRunner)>),
-    MostRecentDayFunc: FnOnce(usize, usize, usize) -> (usize, usize),
 {
-    pub fn new(register_func: RegisterFunc, most_recent_day_func: MostRecentDayFunc) -> Self {
+    pub fn new(register_func: RegisterFunc) -> Self {
+        Self::with_calendar(register_func, AdventOfCode)
+    }
+}
+
+impl<RegisterFunc, C> Config<RegisterFunc, C>
+where
+    RegisterFunc: Fn(&mut BTreeMap<(usize, usize), (u8, super::NewRunner)>),
+    C: Calendar,
+{
+    pub fn with_calendar(register_func: RegisterFunc, calendar: C) -> Self {
         Self {
             download_input: true,
             allow_copy: true,
+            numeric_compare: false,
             readme_header: "",
             register_func,
-            most_recent_day_func,
+            calendar,
         }
     }
 
@@ -45,14 +59,25 @@ where
     pub fn allow_copy(&mut self, allow_copy: bool) {
         self.allow_copy = allow_copy;
     }
+
+    pub fn numeric_compare(&mut self, numeric_compare: bool) {
+        self.numeric_compare = numeric_compare;
+    }
+}
+
+// Directory next to input_files, created if needed
+fn sibling_dir(name: &str) -> Result<PathBuf, Error> {
+    let mut dir = super::search_up("input_files", SearchType::Dir)?;
+    dir.pop();
+    dir.push(name);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
 }
 
-pub fn main<RegisterFunc, MostRecentDayFunc, const N: usize>(
-    config: Config<RegisterFunc, MostRecentDayFunc>,
-) -> Result<(), Error>
+pub fn main<RegisterFunc, C, const N: usize>(config: Config<RegisterFunc, C>) -> Result<(), Error>
 where
     RegisterFunc: Fn(&mut BTreeMap<(usize, usize), (u8, super::NewRunner)>),
-    MostRecentDayFunc: FnOnce(usize, usize, usize) -> (usize, usize),
+    C: Calendar,
 {
     let args = args::Args::new();
 
@@ -66,28 +91,33 @@ where
     } else {
         super::output(|output| output.capture());
     }
+    super::output(|output| output.verbosity(Level::from_verbosity(args.verbose)));
+    if args.log && !args.times {
+        let log_dir = sibling_dir("logs")?;
+        super::output(|output| output.log_dir(Some(log_dir)));
+    }
+    if args.vis_dir && !args.times {
+        let frames_dir = sibling_dir("frames")?;
+        super::output(|output| output.visualise(Some(Visualise::dir(frames_dir))));
+    } else if args.vis && !args.times {
+        super::output(|output| output.visualise(Some(Visualise::terminal(30))));
+    }
 
     let mut times_cache: BTreeMap<usize, Vec<TimesCacheEntry>> = BTreeMap::new();
 
-    use chrono::Datelike;
-    let today = chrono::Local::now();
-    let most_recent_day = (config.most_recent_day_func)(
-        today.year() as usize,
-        today.month() as usize,
-        today.day() as usize,
-    );
+    let most_recent_day = config.calendar.most_recent_day(chrono::Utc::now());
     if config.download_input {
         match &args.run {
             args::Run::Day { year, day } => {
                 if let Err(e) = super::download_input(*year, *day) {
-                    println!("Cannot download input for {year}-{day:02}.  {e:?}");
+                    println!("Cannot download input for {year}-{day:02}.  {e}");
                 }
             }
             _ => {
                 let year = most_recent_day.0;
                 let day = most_recent_day.1;
                 if let Err(e) = super::download_input(year, day) {
-                    println!("Cannot download input for {year}-{day:02}.  {e:?}");
+                    println!("Cannot download input for {year}-{day:02}.  {e}");
                 }
             }
         }
@@ -117,6 +147,7 @@ where
                 *day,
                 part,
                 &input_file_cache,
+                config.numeric_compare,
             );
             times_cache_entry.results.insert(part, result);
         }
@@ -32,6 +32,10 @@ pub(crate) struct Args {
     pub(crate) times: bool,
     pub(crate) md: bool,
     pub(crate) no_capture: bool,
+    pub(crate) verbose: usize,
+    pub(crate) log: bool,
+    pub(crate) vis: bool,
+    pub(crate) vis_dir: bool,
 }
 
 impl Args {
@@ -57,6 +61,10 @@ impl Args {
         std::eprintln!("      --times          Generate Times Table");
         std::eprintln!("      --md             Format Times Table as Markdown");
         std::eprintln!("      --nocapture      Do not capture output");
+        std::eprintln!("  -v, --verbose        Show debug! output, -vv to also show trace!");
+        std::eprintln!("      --log            Write the full output of each part to logs/");
+        std::eprintln!("      --vis            Draw frames in the terminal (needs stdout output)");
+        std::eprintln!("      --vis-dir        Write numbered frames to frames/");
         std::eprintln!("  -h, --help           Print help");
 
         if let Some(err) = err {
@@ -85,13 +93,18 @@ impl Args {
             "executable".into()
         };
 
-        while let Some(arg) = args.next() {
+        for arg in args {
             match arg.as_str() {
                 "--sample-data" | "--sample" => self.sample = true,
                 "--real-data" | "--real" | "--release" => self.sample = false,
                 "--times" => self.times = true,
                 "--md" => self.md = true,
                 "--nocapture" => self.no_capture = true,
+                "-v" | "--verbose" => self.verbose += 1,
+                "-vv" => self.verbose += 2,
+                "--log" => self.log = true,
+                "--vis" => self.vis = true,
+                "--vis-dir" => self.vis_dir = true,
                 "--help" | "-h" => Self::help(exec, None),
                 _ if arg.starts_with("--") => {
                     Self::help(exec, Some(format!("Unknown option {arg:?}").as_str()))
@@ -0,0 +1,179 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc, Weekday};
+
+pub trait Calendar {
+    fn most_recent_day(&self, now: DateTime<Utc>) -> (usize, usize);
+}
+
+// Closures are handed the local (year, month, day)
+impl<F> Calendar for F
+where
+    F: Fn(usize, usize, usize) -> (usize, usize),
+{
+    fn most_recent_day(&self, now: DateTime<Utc>) -> (usize, usize) {
+        let today = now.with_timezone(&chrono::Local);
+        self(
+            today.year() as usize,
+            today.month() as usize,
+            today.day() as usize,
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AdventOfCode;
+
+impl AdventOfCode {
+    pub fn days(year: usize) -> usize {
+        if year >= 2025 {
+            12
+        } else {
+            25
+        }
+    }
+}
+
+impl Calendar for AdventOfCode {
+    fn most_recent_day(&self, now: DateTime<Utc>) -> (usize, usize) {
+        // Puzzles unlock at midnight EST (UTC-5)
+        let today = now.with_timezone(&FixedOffset::west_opt(5 * 3600).unwrap());
+        let year = today.year() as usize;
+        if today.month() == 12 {
+            (year, (today.day() as usize).min(Self::days(year)))
+        } else {
+            (year - 1, Self::days(year - 1))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EverybodyCodes;
+
+impl EverybodyCodes {
+    pub const QUESTS: usize = 20;
+
+    pub fn first_quest(year: usize) -> NaiveDate {
+        NaiveDate::from_weekday_of_month_opt(year as i32, 11, Weekday::Mon, 1).unwrap()
+    }
+}
+
+impl Calendar for EverybodyCodes {
+    fn most_recent_day(&self, now: DateTime<Utc>) -> (usize, usize) {
+        // Quests unlock at 23:00 UTC, on weekdays, starting the first Monday of November
+        let today = now.with_timezone(&FixedOffset::east_opt(3600).unwrap());
+        let year = today.year() as usize;
+        let first = Self::first_quest(year);
+        if today.date_naive() < first {
+            return (year - 1, Self::QUESTS);
+        }
+
+        let quests = first
+            .iter_days()
+            .take_while(|d| *d <= today.date_naive())
+            .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+            .count();
+        (year, quests.min(Self::QUESTS))
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Custom {
+    offset: FixedOffset,
+    month: u32,
+    days: usize,
+}
+
+impl Custom {
+    pub fn new(utc_offset_hours: i32, month: u32, days: usize) -> Self {
+        assert!((1..=12).contains(&month), "Invalid month {month}");
+        Self {
+            offset: FixedOffset::east_opt(utc_offset_hours * 3600).expect("Invalid UTC offset"),
+            month,
+            days,
+        }
+    }
+}
+
+impl Calendar for Custom {
+    fn most_recent_day(&self, now: DateTime<Utc>) -> (usize, usize) {
+        // One day unlocks each midnight, starting on the 1st of `month`
+        let today = now.with_timezone(&self.offset);
+        let year = today.year() as usize;
+        if today.month() < self.month {
+            (year - 1, self.days)
+        } else if today.month() == self.month {
+            (year, (today.day() as usize).min(self.days))
+        } else {
+            (year, self.days)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn advent_of_code() {
+        let aoc = AdventOfCode;
+
+        // Before the event, the last year is fully available
+        assert_eq!(aoc.most_recent_day(utc(2024, 6, 1, 0, 0)), (2023, 25));
+        assert_eq!(aoc.most_recent_day(utc(2025, 12, 1, 4, 59)), (2024, 25));
+
+        // Day 1 unlocks at 05:00 UTC
+        assert_eq!(aoc.most_recent_day(utc(2025, 12, 1, 5, 0)), (2025, 1));
+        assert_eq!(aoc.most_recent_day(utc(2024, 12, 10, 4, 59)), (2024, 9));
+        assert_eq!(aoc.most_recent_day(utc(2024, 12, 10, 5, 0)), (2024, 10));
+
+        // 25 days until 2024, 12 from 2025
+        assert_eq!(aoc.most_recent_day(utc(2024, 12, 31, 12, 0)), (2024, 25));
+        assert_eq!(aoc.most_recent_day(utc(2025, 12, 20, 12, 0)), (2025, 12));
+        assert_eq!(aoc.most_recent_day(utc(2026, 1, 1, 4, 0)), (2025, 12));
+        assert_eq!(aoc.most_recent_day(utc(2026, 3, 1, 0, 0)), (2025, 12));
+    }
+
+    #[test]
+    fn everybody_codes() {
+        let ec = EverybodyCodes;
+
+        assert_eq!(
+            EverybodyCodes::first_quest(2024),
+            NaiveDate::from_ymd_opt(2024, 11, 4).unwrap()
+        );
+        assert_eq!(ec.most_recent_day(utc(2024, 11, 3, 22, 59)), (2023, 20));
+
+        // Quest 1 unlocks at 23:00 UTC the evening before
+        assert_eq!(ec.most_recent_day(utc(2024, 11, 3, 23, 0)), (2024, 1));
+        assert_eq!(ec.most_recent_day(utc(2024, 11, 8, 12, 0)), (2024, 5));
+
+        // Nothing unlocks over the weekend
+        assert_eq!(ec.most_recent_day(utc(2024, 11, 9, 12, 0)), (2024, 5));
+        assert_eq!(ec.most_recent_day(utc(2024, 11, 10, 22, 0)), (2024, 5));
+        assert_eq!(ec.most_recent_day(utc(2024, 11, 10, 23, 0)), (2024, 6));
+
+        // Last quest
+        assert_eq!(ec.most_recent_day(utc(2024, 11, 29, 0, 0)), (2024, 20));
+        assert_eq!(ec.most_recent_day(utc(2024, 12, 25, 0, 0)), (2024, 20));
+    }
+
+    #[test]
+    fn custom() {
+        let custom = Custom::new(0, 3, 10);
+
+        assert_eq!(custom.most_recent_day(utc(2024, 2, 28, 23, 59)), (2023, 10));
+        assert_eq!(custom.most_recent_day(utc(2024, 3, 1, 0, 0)), (2024, 1));
+        assert_eq!(custom.most_recent_day(utc(2024, 3, 9, 12, 0)), (2024, 9));
+        assert_eq!(custom.most_recent_day(utc(2024, 3, 25, 12, 0)), (2024, 10));
+        assert_eq!(custom.most_recent_day(utc(2024, 8, 1, 0, 0)), (2024, 10));
+
+        let custom = Custom::new(9, 3, 10);
+        assert_eq!(custom.most_recent_day(utc(2024, 2, 29, 14, 59)), (2023, 10));
+        assert_eq!(custom.most_recent_day(utc(2024, 2, 29, 15, 0)), (2024, 1));
+    }
+}
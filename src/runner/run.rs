@@ -1,24 +1,37 @@
 use std::time::{Duration, Instant};
 
-use crate::{output, Error, InputFileCache, NewRunner, YearDayPart};
+use crate::{output, Error, InputFileCache, InputPosition, NewRunner, YearDayPart};
 use colored::Colorize;
 
+fn print_position(ydp: YearDayPart, pos: &InputPosition) {
+    let Some(text) = &pos.text else {
+        return;
+    };
+    let line = pos.line.map(|l| l.to_string()).unwrap_or_default();
+    println!("{ydp}:   {line:>5} | {text}");
+    if let Some(column) = pos.column {
+        let pad = " ".repeat(column.saturating_sub(1));
+        println!("{ydp}:   {:>5} | {pad}{}", "", "^".bright_red());
+    }
+}
+
 fn run_part(
     new_runner: &NewRunner,
     part: u8,
     input: impl AsRef<[u8]>,
     expect: Option<impl AsRef<[u8]>>,
+    numeric_compare: bool,
 ) -> Result<String, Error> {
     let mut runner = new_runner();
     runner.parse(input.as_ref(), part)?;
-    let output = runner.run_part(part)?;
+    let run_output = runner.run_part(part)?;
 
-    let output = output.to_string();
+    let output = run_output.to_string();
     let output = output.trim_end_matches('\n');
     if let Some(expect) = expect {
         let expect = std::str::from_utf8(expect.as_ref())?;
-        let expect = expect.trim_end_matches('\n');
-        if expect == output {
+        let expect = expect.trim_end_matches(['\r', '\n']);
+        if run_output.matches(expect, numeric_compare) {
             Ok(output.to_string())
         } else {
             Err(Error::WrongAnswer(output.to_string(), expect.to_string()))
@@ -37,6 +50,7 @@ pub fn run<const N: usize>(
     day: usize,
     part: u8,
     input_file_cache: &InputFileCache<N>,
+    numeric_compare: bool,
 ) -> Result<Duration, Error> {
     let ydp = YearDayPart::new(year, day, part as usize);
 
@@ -58,7 +72,7 @@ pub fn run<const N: usize>(
             } else {
                 None
             };
-            let result = run_part(new_runner, part, &input, expect.as_ref());
+            let result = run_part(new_runner, part, &input, expect.as_ref(), numeric_compare);
 
             elapsed.push(start.elapsed());
 
@@ -116,7 +130,13 @@ pub fn run<const N: usize>(
                         println!("{ydp}: {}", "skipped".bright_yellow());
                     }
                     Err(e) => {
-                        println!("{ydp}: Error: {}", format!("{e:?}").bright_red());
+                        println!("{ydp}: Error: {}", e.to_string().bright_red());
+                        if let Some(pos) = e.position() {
+                            print_position(ydp, pos);
+                        }
+                        for cause in e.causes() {
+                            println!("{ydp}:   Caused by: {cause}");
+                        }
                     }
                 }
                 println!("{ydp}: {elapsed:?}", elapsed = elapsed.last().unwrap());
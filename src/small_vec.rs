@@ -98,7 +98,7 @@ where
         self.len() == 0
     }
 
-    pub fn iter(&self) -> SmallVecIter<T, K, LEN> {
+    pub fn iter(&self) -> SmallVecIter<'_, T, K, LEN> {
         self.into_iter()
     }
 
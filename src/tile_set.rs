@@ -34,7 +34,7 @@ impl<T: Integer> TileSet<T> {
         todo!("remove_tile not done yet");
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Tile<T>> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Tile<T>> {
         self.into_iter()
     }
 }